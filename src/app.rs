@@ -1,19 +1,50 @@
-use crossterm::event::KeyEvent;
+use color_eyre::eyre;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use std::{
+    collections::{HashSet, VecDeque},
+    path::Path,
+    process::{Command, ExitStatus},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    time::{Duration, Instant},
+};
+use tracing::debug;
+
 use ratatui::{
     layout::{Constraint, Flex, Layout, Rect},
     Frame,
 };
 
 use crate::{
-    cli,
+    cli, clipboard,
     components::{
-        Action, ConfirmComponent, CreateWorktreeComponent, EventState, HelpComponent, HelpEntry,
-        PrWorktreeComponent, RepositoriesComponent, SelectDirectoryComponent, WorktreesComponent,
+        is_valid_branch_name, Action, CloneProgressComponent, ConfirmComponent, CreatePrComponent,
+        CreateWorktreeComponent, EventState, HelpComponent, HelpEntry, LogComponent,
+        MetadataComponent, Outcome, PrWorktreeComponent, RepositoriesComponent,
+        RunCommandComponent, SelectDirectoryComponent, SummaryComponent, TicketComponent,
+        WorktreesComponent,
     },
+    config,
+    exec::{self, ExecEvent},
     git, github,
+    history::{self, History},
+    hooks::{self, HookContext, HookEvent},
     keymap::{self, InputMode},
+    metadata::MetadataStore,
+    pr_status::{self, PrLookup, PrStatusCache},
+    sessions::Multiplexer,
+    theme::{self, Theme},
+    tracker,
 };
 
+/// How often the PR status of every worktree is looked up again.
+const PR_STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How often the live tmux / zellij sessions are listed again.
+const SESSIONS_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Two clicks on the same cell within this interval make a double-click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
 #[derive(Debug, Clone, Copy)]
 pub enum Focus {
     Worktrees,
@@ -23,12 +54,131 @@ pub enum Focus {
     Help,
     PrWorktree,
     SelectReposDir,
+    CreatePr,
+    Cloning,
+    Metadata,
+    Ticket,
+    Summary,
+    RunCommand,
+    CommandOutput,
+}
+
+/// A clone running in the background for a PR whose repository was not found.
+struct PendingClone {
+    events: Receiver<github::CloneEvent>,
+    repos_dir: String,
+    pr_url: github::PrUrl,
+    pr_info: github::PrInfo,
+    auto: bool,
+}
+
+/// An issue fetched from the tracker whose worktree is being created.
+struct PendingTicket {
+    issue: tracker::Issue,
+    branch: String,
+}
+
+/// A fetch or pull of worktrees running in the background, with the outcomes
+/// so far.
+struct BatchRun {
+    operation: BatchOperation,
+    /// The path of each worktree done, with its outcome.
+    events: Receiver<(String, Outcome)>,
+    outcomes: Vec<Outcome>,
+}
+
+/// Worktrees being deleted one after another, as their hooks run in the
+/// background, with the outcomes so far.
+struct DeleteRun {
+    operation: BatchOperation,
+    queue: VecDeque<String>,
+    /// The worktree whose hooks are running.
+    current: Option<DeleteHooks>,
+    outcomes: Vec<Outcome>,
+}
+
+/// The `pre_delete` or `post_delete` hooks of a worktree, running in the
+/// background.
+struct DeleteHooks {
+    path: String,
+    label: String,
+    context: HookContext,
+    /// Run once the worktree is deleted.
+    post_delete: Vec<String>,
+    events: Receiver<HookEvent>,
+    errors: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ConfirmAction {
     DeleteWorktree,
     CloneRepo,
+    Batch(BatchOperation),
+}
+
+/// An operation applied to every marked worktree, or to the selected one when
+/// none is marked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BatchOperation {
+    Delete,
+    /// Deletes even when a `pre_delete` hook fails.
+    ForceDelete,
+    /// Deletes the worktrees whose remote branch is gone.
+    Prune,
+    Fetch,
+    Pull,
+    OpenInEditor,
+    OpenTerminal,
+}
+
+impl BatchOperation {
+    fn title(self) -> &'static str {
+        match self {
+            BatchOperation::Delete | BatchOperation::ForceDelete => "Delete Worktrees",
+            BatchOperation::Prune => "Prune Worktrees",
+            BatchOperation::Fetch => "Fetch",
+            BatchOperation::Pull => "Pull",
+            BatchOperation::OpenInEditor => "Open in Editor",
+            BatchOperation::OpenTerminal => "Open Terminal",
+        }
+    }
+
+    fn verb(self) -> &'static str {
+        match self {
+            BatchOperation::Delete | BatchOperation::ForceDelete | BatchOperation::Prune => {
+                "Delete"
+            }
+            BatchOperation::Fetch => "Fetch",
+            BatchOperation::Pull => "Pull",
+            BatchOperation::OpenInEditor | BatchOperation::OpenTerminal => "Open",
+        }
+    }
+
+    /// Status line while the operation runs in the background.
+    fn progress(self) -> &'static str {
+        match self {
+            BatchOperation::Delete | BatchOperation::ForceDelete | BatchOperation::Prune => {
+                "Deleting"
+            }
+            BatchOperation::Fetch => "Fetching",
+            BatchOperation::Pull => "Pulling",
+            BatchOperation::OpenInEditor | BatchOperation::OpenTerminal => "Opening",
+        }
+    }
+
+    fn detail(self) -> &'static str {
+        match self {
+            BatchOperation::Delete => "Directories and uncommitted changes are removed",
+            BatchOperation::ForceDelete => "Also when a pre_delete hook fails",
+            BatchOperation::Prune => "Their remote branches are gone; directories are removed",
+            BatchOperation::Fetch => "Fetches origin once per repository",
+            BatchOperation::Pull => "Fast-forward only; dirty worktrees are skipped",
+            BatchOperation::OpenInEditor => {
+                "Each worktree opens in the editor, one after another in this terminal"
+            }
+            BatchOperation::OpenTerminal => "Each worktree opens in a new terminal window",
+        }
+    }
 }
 
 pub struct App {
@@ -39,30 +189,84 @@ pub struct App {
     help_component: HelpComponent,
     pr_worktree_component: PrWorktreeComponent,
     select_directory_component: SelectDirectoryComponent,
+    create_pr_component: CreatePrComponent,
+    clone_progress_component: CloneProgressComponent,
+    metadata_component: MetadataComponent,
+    ticket_component: TicketComponent,
+    summary_component: SummaryComponent,
+    log_component: LogComponent,
+    run_command_component: RunCommandComponent,
     args: cli::Args,
+    config: config::Config,
+    github: github::GithubClient,
     focus: Focus,
     previous_focus: Focus,
     mode: InputMode,
     confirm_action: ConfirmAction,
+    /// Paths of the worktrees a confirmed batch operation applies to.
+    pending_batch: Vec<String>,
     pending_pr: Option<(github::PrUrl, github::PrInfo)>,
     pending_clone_auto: bool,
+    pending_clone: Option<PendingClone>,
+    pending_ticket: Option<PendingTicket>,
+    /// Hooks running in the background, with the worktree they run for.
+    hook_runs: Vec<(String, Receiver<HookEvent>)>,
+    /// Output of the command typed with `!`, while it runs.
+    command_run: Option<Receiver<ExecEvent>>,
+    batch_run: Option<BatchRun>,
+    delete_run: Option<DeleteRun>,
+    pr_status_cache: PrStatusCache,
+    metadata_store: MetadataStore,
+    history: History,
+    pr_status_refresh: Option<Receiver<(PrLookup, Option<github::PrStatus>)>>,
+    last_pr_status_refresh: Option<Instant>,
+    /// When and where the left button was last pressed, to detect double-clicks.
+    last_click: Option<(Instant, u16, u16)>,
+    /// Commands waiting to be handed the terminal, such as terminal editors.
+    foreground_commands: VecDeque<Command>,
+    last_sessions_refresh: Option<Instant>,
     pub selected_path: Option<String>,
 }
 
 impl App {
-    pub fn new() -> App {
-        let args = cli::Args::new();
+    pub fn new() -> eyre::Result<App> {
+        Ok(Self::from_args(cli::Args::try_new()?))
+    }
+
+    /// The app for `args`, with the config file and GitHub from the
+    /// environment.
+    pub fn from_args(args: cli::Args) -> App {
+        let (config, config_error) = match config::Config::load() {
+            Ok(config) => (config, None),
+            Err(e) => (config::Config::default(), Some(format!("{:#}", e))),
+        };
+        let mut app = Self::with_args(args, config, github::GithubClient::from_env());
+        if config_error.is_some() {
+            app.worktrees_component.last_error = config_error;
+        }
+        app
+    }
+
+    pub fn with_args(args: cli::Args, config: config::Config, github: github::GithubClient) -> App {
         let repositories: Vec<_> = args
             .repos_dirs
             .iter()
             .flat_map(|dir| git::list_repositories(dir, args.run_fetch))
             .collect();
         let worktrees = git::worktrees_of_repositories(&repositories);
+        let data_dir = args.data_dir.clone();
 
         let repositories_component = RepositoriesComponent::new(repositories);
         let worktrees_component = WorktreesComponent::new(worktrees, args.worktrees_dir.clone());
         let select_directory_component = SelectDirectoryComponent::new(args.repos_dirs.clone());
-        Self {
+        let theme_error = match Theme::from_config(&config.theme, theme::no_color_requested()) {
+            Ok(theme) => {
+                theme::set(theme);
+                None
+            }
+            Err(e) => Some(format!("{:#}", e)),
+        };
+        let mut app = Self {
             worktrees_component,
             repositories_component,
             create_worktree: CreateWorktreeComponent::new(String::new()),
@@ -70,28 +274,359 @@ impl App {
             help_component: HelpComponent::new(vec![]),
             pr_worktree_component: PrWorktreeComponent::new(),
             select_directory_component,
+            create_pr_component: CreatePrComponent::new(),
+            clone_progress_component: CloneProgressComponent::new(String::new()),
+            metadata_component: MetadataComponent::new(String::new(), &Default::default()),
+            ticket_component: TicketComponent::new(),
+            summary_component: SummaryComponent::new(String::new(), vec![]),
+            log_component: LogComponent::new(),
+            run_command_component: RunCommandComponent::new(vec![]),
             focus: Focus::Worktrees,
             previous_focus: Focus::Worktrees,
             args,
+            config,
+            github,
             mode: InputMode::Normal,
             confirm_action: ConfirmAction::DeleteWorktree,
+            pending_batch: vec![],
             pending_pr: None,
             pending_clone_auto: false,
+            pending_clone: None,
+            pending_ticket: None,
+            hook_runs: vec![],
+            command_run: None,
+            batch_run: None,
+            delete_run: None,
+            pr_status_cache: PrStatusCache::default(),
+            metadata_store: MetadataStore::load(data_dir.as_deref()),
+            history: History::load(data_dir.as_deref()),
+            pr_status_refresh: None,
+            last_pr_status_refresh: None,
+            last_click: None,
+            foreground_commands: VecDeque::new(),
+            last_sessions_refresh: None,
             selected_path: None,
+        };
+        let metadata: Vec<_> = app
+            .worktrees_component
+            .worktrees()
+            .iter()
+            .filter_map(|wt| {
+                let metadata = app.metadata_store.get(&wt.repository_name()?, wt.name())?;
+                Some((wt.path().to_string(), metadata.clone()))
+            })
+            .collect();
+        for (path, metadata) in metadata {
+            app.worktrees_component.set_metadata(path, Some(metadata));
+        }
+        app.worktrees_component
+            .set_usage(app.history.usage().clone());
+        app.worktrees_component
+            .set_sort_mode(app.history.sort_mode());
+        app.worktrees_component.set_grouped(app.history.grouped());
+        if !app.args.no_pr_status {
+            app.pr_status_cache = PrStatusCache::load(app.args.data_dir.as_deref());
+            for lookup in app.pr_lookups() {
+                let status = app.pr_status_cache.get(&lookup).cloned();
+                app.worktrees_component
+                    .set_pr_status(lookup.worktree_path, status);
+            }
+        }
+        if theme_error.is_some() {
+            app.worktrees_component.last_error = theme_error;
+        }
+        app
+    }
+
+    /// The next command that needs the terminal; the TUI is suspended while
+    /// it runs.
+    pub fn take_foreground_command(&mut self) -> Option<Command> {
+        self.foreground_commands.pop_front()
+    }
+
+    pub fn foreground_command_finished(
+        &mut self,
+        command: &Command,
+        result: std::io::Result<ExitStatus>,
+    ) {
+        let program = command.get_program().to_string_lossy();
+        let error = match result {
+            Ok(status) if status.success() => return,
+            Ok(status) => format!("{} exited with {}", program, status),
+            Err(e) => format!("Could not run {}: {}", program, e),
+        };
+        self.worktrees_component.last_error = Some(error);
+    }
+
+    /// Called on every iteration of the event loop to apply the results of
+    /// background work.
+    pub fn on_tick(&mut self) {
+        self.poll_clone();
+        self.poll_pr_statuses();
+        self.poll_hooks();
+        self.poll_command_run();
+        self.poll_batch_run();
+        self.poll_delete_run();
+        if self
+            .last_sessions_refresh
+            .is_none_or(|at| at.elapsed() >= SESSIONS_REFRESH_INTERVAL)
+        {
+            self.refresh_sessions();
+        }
+    }
+
+    /// Marks the worktrees whose tmux / zellij session is running.
+    fn refresh_sessions(&mut self) {
+        self.last_sessions_refresh = Some(Instant::now());
+        let Some(multiplexer) = self.config.sessions.multiplexer else {
+            return;
+        };
+        let live = multiplexer.live_sessions();
+        let paths = self
+            .worktrees_component
+            .worktrees()
+            .iter()
+            .map(|wt| wt.path().to_string())
+            .filter(|path| live.contains(&self.session_name(multiplexer, path)))
+            .collect();
+        self.worktrees_component.set_sessions(paths);
+    }
+
+    fn session_name(&self, multiplexer: Multiplexer, path: &str) -> String {
+        let branch = self
+            .worktrees_component
+            .worktrees()
+            .iter()
+            .find(|wt| wt.path() == path)
+            .map(|wt| wt.branch.clone())
+            .unwrap_or_default();
+        multiplexer.session_name(&self.worktrees_component.repository_of(path), &branch)
+    }
+
+    /// Creates or switches to the session of the worktree at `path`; attaching
+    /// from outside the multiplexer hands it the terminal.
+    fn open_session(&mut self, multiplexer: Multiplexer, path: &str) -> eyre::Result<()> {
+        let name = self.session_name(multiplexer, path);
+        if let Some(attach) = multiplexer.open(&name, path)? {
+            self.foreground_commands.push_back(attach);
+        }
+        self.refresh_sessions();
+        Ok(())
+    }
+
+    /// Logs the untracked files that could not be brought into a new worktree.
+    fn report_untracked_errors(&mut self, errors: Vec<String>) {
+        if errors.is_empty() {
+            return;
+        }
+        self.worktrees_component.last_error = Some(format!(
+            "Could not bring {} untracked file(s) into the worktree — L shows the log",
+            errors.len()
+        ));
+        for error in errors {
+            self.log_component.push_error(error);
+        }
+        self.log_component.visible = true;
+    }
+
+    /// Starts the `post_create` hooks of a new worktree in the background.
+    /// A hook that fails is reported, but the worktree is kept.
+    fn run_post_create_hooks(&mut self, worktree: &git::Worktree) {
+        let (Some(repo), Some(repo_path)) =
+            (worktree.repository_name(), worktree.repository_path())
+        else {
+            return;
+        };
+        let hooks = match self.config.hooks(&repo, &repo_path) {
+            Ok(hooks) => hooks,
+            Err(e) => {
+                self.worktrees_component.last_error = Some(format!("{:#}", e));
+                return;
+            }
+        };
+        if !self.config.trusts_repo_hooks(&repo) && repo_path.join(hooks::REPO_FILE).exists() {
+            self.log_component.push_info(format!(
+                "Ignoring the hooks in {} of {} — set trust_hooks = true under [repos.{}] to run them",
+                hooks::REPO_FILE,
+                repo,
+                repo
+            ));
+            self.log_component.visible = true;
+        }
+        let commands = hooks.post_create.unwrap_or_default();
+        if commands.is_empty() {
+            return;
+        }
+
+        let Some(context) = HookContext::for_worktree(worktree, "post_create") else {
+            return;
+        };
+        let label = format!("{} / {}", repo, worktree.branch);
+        self.log_component
+            .push_info(format!("Running post_create hooks of {}", label));
+        self.log_component.visible = true;
+        self.hook_runs
+            .push((label, hooks::run_in_background(commands, context, false)));
+    }
+
+    fn poll_hooks(&mut self) {
+        let mut runs = std::mem::take(&mut self.hook_runs);
+        runs.retain(|(label, events)| loop {
+            match events.try_recv() {
+                Ok(HookEvent::Failed(error)) => {
+                    self.log_component.push_error(error.clone());
+                    self.worktrees_component.last_error = Some(format!(
+                        "Hook of {} failed: {} — the worktree was kept, L shows the log",
+                        label, error
+                    ));
+                }
+                Ok(HookEvent::Finished) | Err(TryRecvError::Disconnected) => break false,
+                Ok(event) => self.log_hook_event(event),
+                Err(TryRecvError::Empty) => break true,
+            }
+        });
+        self.hook_runs.append(&mut runs);
+    }
+
+    fn poll_command_run(&mut self) {
+        let Some(events) = &self.command_run else {
+            return;
+        };
+        loop {
+            match events.try_recv() {
+                Ok(ExecEvent::Output(label, line)) => {
+                    self.run_command_component.push_output(&label, line)
+                }
+                Ok(ExecEvent::Finished(label, result)) => {
+                    self.run_command_component.push_result(label, result)
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.command_run = None;
+                    let (passed, failed) = self.run_command_component.finish();
+                    self.worktrees_component.last_error = Some(format!(
+                        "`{}` passed in {} and failed in {} worktree(s)",
+                        self.run_command_component.command().unwrap_or_default(),
+                        passed,
+                        failed
+                    ));
+                    return;
+                }
+            }
+        }
+    }
+
+    fn log_hook_event(&mut self, event: HookEvent) {
+        match event {
+            HookEvent::Started(command) => self.log_component.push_command(&command),
+            HookEvent::Output(line) => self.log_component.push_output(line),
+            HookEvent::Failed(error) => self.log_component.push_error(error),
+            HookEvent::Finished => {}
+        }
+    }
+
+    fn poll_clone(&mut self) {
+        let Some(pending) = &self.pending_clone else {
+            return;
+        };
+        loop {
+            match pending.events.try_recv() {
+                Ok(github::CloneEvent::Progress(progress)) => {
+                    self.clone_progress_component.update(progress)
+                }
+                Ok(github::CloneEvent::Finished(result)) => {
+                    if let Some(pending) = self.pending_clone.take() {
+                        self.handle_clone_finished(pending, result);
+                    }
+                    return;
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.pending_clone = None;
+                    self.worktrees_component.last_error =
+                        Some("Clone stopped unexpectedly".to_string());
+                    self.focus = Focus::Worktrees;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Applies the results of the background PR status refresh and starts a
+    /// new one when due.
+    fn poll_pr_statuses(&mut self) {
+        if self.args.no_pr_status {
+            return;
+        }
+
+        if let Some(receiver) = &self.pr_status_refresh {
+            loop {
+                match receiver.try_recv() {
+                    Ok((lookup, status)) => {
+                        self.pr_status_cache.insert(&lookup, status.clone());
+                        self.worktrees_component
+                            .set_pr_status(lookup.worktree_path, status);
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        self.pr_status_refresh = None;
+                        if let Err(e) = self.pr_status_cache.save() {
+                            debug!("Could not save PR status cache: {:#}", e);
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        let refresh_due = self
+            .last_pr_status_refresh
+            .is_none_or(|at| at.elapsed() >= PR_STATUS_REFRESH_INTERVAL);
+        if self.pr_status_refresh.is_none() && refresh_due {
+            self.refresh_pr_statuses();
         }
     }
 
+    fn refresh_pr_statuses(&mut self) {
+        self.last_pr_status_refresh = Some(Instant::now());
+        self.pr_status_refresh = Some(pr_status::spawn_refresh(
+            self.github.clone(),
+            self.pr_lookups(),
+        ));
+    }
+
+    /// Builds the PR lookups for every worktree whose repository has a GitHub `origin`.
+    fn pr_lookups(&self) -> Vec<PrLookup> {
+        self.worktrees_component
+            .worktrees()
+            .iter()
+            .filter_map(PrLookup::for_worktree)
+            .collect()
+    }
+
     pub fn draw(&mut self, frame: &mut Frame) {
         let [full_area] = Layout::default()
             .constraints([Constraint::Percentage(100)])
             .areas(frame.area());
 
+        let [worktrees_area, log_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(if self.log_component.visible {
+                LogComponent::HEIGHT
+            } else {
+                0
+            }),
+        ])
+        .areas(full_area);
         self.worktrees_component.draw(
             frame,
-            full_area,
+            worktrees_area,
             self.mode,
             matches!(self.focus, Focus::Worktrees),
         );
+        if self.log_component.visible {
+            self.log_component.draw(frame, log_area);
+        }
 
         let show_repos = matches!(self.focus, Focus::Repositories)
             || matches!(
@@ -105,9 +640,10 @@ impl App {
         }
 
         if let Focus::CreateWorktree = self.focus {
-            let [popup_area] = Layout::vertical([Constraint::Length(9)])
-                .flex(Flex::Center)
-                .areas(full_area);
+            let [popup_area] =
+                Layout::vertical([Constraint::Length(self.create_worktree.height())])
+                    .flex(Flex::Center)
+                    .areas(full_area);
             let [popup_area] = Layout::horizontal([Constraint::Percentage(55)])
                 .flex(Flex::Center)
                 .areas(popup_area);
@@ -115,15 +651,43 @@ impl App {
         }
 
         if let Focus::Confirm = self.focus {
-            let [popup_area] = Layout::vertical([Constraint::Length(8)])
-                .flex(Flex::Center)
-                .areas(full_area);
+            let [popup_area] =
+                Layout::vertical([Constraint::Length(self.confirm_component.height())])
+                    .flex(Flex::Center)
+                    .areas(full_area);
             let [popup_area] = Layout::horizontal([Constraint::Percentage(55)])
                 .flex(Flex::Center)
                 .areas(popup_area);
             self.confirm_component.draw(frame, popup_area);
         }
 
+        if let Focus::Summary = self.focus {
+            let [popup_area] =
+                Layout::vertical([Constraint::Length(self.summary_component.height())])
+                    .flex(Flex::Center)
+                    .areas(full_area);
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            self.summary_component.draw(frame, popup_area);
+        }
+
+        if let Focus::RunCommand = self.focus {
+            let [popup_area] =
+                Layout::vertical([Constraint::Length(RunCommandComponent::PROMPT_HEIGHT)])
+                    .flex(Flex::Center)
+                    .areas(full_area);
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(60)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            self.run_command_component.draw_prompt(frame, popup_area);
+        }
+
+        if let Focus::CommandOutput = self.focus {
+            let popup_area = self.popup_area(full_area, 85, 80);
+            self.run_command_component.draw_output(frame, popup_area);
+        }
+
         if let Focus::Help = self.focus {
             let (w, h) = self.help_component.dimensions();
             let popup_area = self.popup_area_fixed(full_area, w, h);
@@ -131,7 +695,7 @@ impl App {
         }
 
         if let Focus::PrWorktree = self.focus {
-            let [popup_area] = Layout::vertical([Constraint::Length(9)])
+            let [popup_area] = Layout::vertical([Constraint::Length(10)])
                 .flex(Flex::Center)
                 .areas(full_area);
             let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
@@ -150,6 +714,46 @@ impl App {
                 .areas(popup_area);
             self.select_directory_component.draw(frame, popup_area);
         }
+
+        if let Focus::CreatePr = self.focus {
+            let [popup_area] = Layout::vertical([Constraint::Length(CreatePrComponent::height())])
+                .flex(Flex::Center)
+                .areas(full_area);
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            self.create_pr_component.draw(frame, popup_area);
+        }
+
+        if let Focus::Cloning = self.focus {
+            let [popup_area] = Layout::vertical([Constraint::Length(6)])
+                .flex(Flex::Center)
+                .areas(full_area);
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(55)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            self.clone_progress_component.draw(frame, popup_area);
+        }
+
+        if let Focus::Ticket = self.focus {
+            let [popup_area] = Layout::vertical([Constraint::Length(10)])
+                .flex(Flex::Center)
+                .areas(full_area);
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            self.ticket_component.draw(frame, popup_area);
+        }
+
+        if let Focus::Metadata = self.focus {
+            let [popup_area] = Layout::vertical([Constraint::Length(MetadataComponent::height())])
+                .flex(Flex::Center)
+                .areas(full_area);
+            let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+            self.metadata_component.draw(frame, popup_area);
+        }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> EventState {
@@ -166,6 +770,77 @@ impl App {
             Focus::Help => self.handle_help_action(action),
             Focus::PrWorktree => self.handle_pr_worktree_action(action),
             Focus::SelectReposDir => self.handle_select_repos_dir_action(action),
+            Focus::CreatePr => self.handle_create_pr_action(action),
+            Focus::Cloning => self.handle_cloning_action(action),
+            Focus::Metadata => self.handle_metadata_action(action),
+            Focus::Ticket => self.handle_ticket_action(action),
+            Focus::Summary => self.handle_summary_action(action),
+            Focus::RunCommand | Focus::CommandOutput => self.handle_run_command_action(action),
+        }
+    }
+
+    /// Clicks select list rows, double-clicks also open them, the wheel moves
+    /// the selection, and the confirmation popup's hints are buttons.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) -> EventState {
+        let (column, row) = (mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+                let down = mouse.kind == MouseEventKind::ScrollDown;
+                match self.focus {
+                    Focus::Worktrees => self.worktrees_component.scroll(down),
+                    Focus::Repositories => self.repositories_component.scroll(down),
+                    Focus::SelectReposDir => self.select_directory_component.scroll(down),
+                    Focus::Help => self.help_component.scroll(down),
+                    Focus::CommandOutput => self.run_command_component.scroll(down),
+                    Focus::Summary => {
+                        let action = if down {
+                            Action::MoveDown
+                        } else {
+                            Action::MoveUp
+                        };
+                        self.summary_component.handle_action(action);
+                    }
+                    _ => return EventState::NotConsumed,
+                }
+                EventState::Consumed
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                let now = Instant::now();
+                let double_click = self.last_click.is_some_and(|(at, c, r)| {
+                    (c, r) == (column, row) && now.duration_since(at) < DOUBLE_CLICK_INTERVAL
+                });
+                self.last_click = (!double_click).then_some((now, column, row));
+
+                match self.focus {
+                    Focus::Worktrees if self.worktrees_component.click(column, row) => {
+                        self.mode = InputMode::Normal;
+                        self.worktrees_component.focus_list();
+                        if double_click {
+                            return self.handle_worktrees_action(Action::Select);
+                        }
+                    }
+                    Focus::Repositories if self.repositories_component.click(column, row) => {
+                        self.mode = InputMode::Normal;
+                        self.repositories_component.focus_list();
+                        if double_click {
+                            return self.handle_repositories_action(Action::Select);
+                        }
+                    }
+                    Focus::SelectReposDir if self.select_directory_component.click(column, row) => {
+                        if double_click {
+                            return self.handle_select_repos_dir_action(Action::Select);
+                        }
+                    }
+                    Focus::Confirm => {
+                        if let Some(action) = self.confirm_component.button_at(column, row) {
+                            return self.handle_confirm_action(action);
+                        }
+                    }
+                    _ => return EventState::NotConsumed,
+                }
+                EventState::Consumed
+            }
+            _ => EventState::NotConsumed,
         }
     }
 
@@ -180,6 +855,12 @@ impl App {
                 EventState::Consumed
             }
             Action::OpenRepositories => {
+                self.pending_ticket = None;
+                if let Some(repo) = self.worktrees_component.selected_repository_header() {
+                    if self.repositories_component.select_repository_by_name(&repo) {
+                        self.repositories_component.focus_list();
+                    }
+                }
                 self.focus = Focus::Repositories;
                 self.mode = InputMode::Normal;
                 EventState::Consumed
@@ -197,21 +878,152 @@ impl App {
                 self.mode = InputMode::Insert;
                 EventState::Consumed
             }
+            Action::OpenCreatePr => {
+                if let Some(worktree) = self.worktrees_component.selected_worktree_mut() {
+                    let base = worktree
+                        .default_base_branch()
+                        .unwrap_or_else(|| "main".to_string());
+                    let commit_messages =
+                        worktree.commit_messages_since(&base).unwrap_or_else(|e| {
+                            debug!("Could not list commits to prefill the PR: {:#}", e);
+                            vec![]
+                        });
+                    self.create_pr_component = CreatePrComponent::from_commits(
+                        worktree.branch.clone(),
+                        base,
+                        &commit_messages,
+                    );
+                    self.focus = Focus::CreatePr;
+                    self.mode = InputMode::Insert;
+                }
+                EventState::Consumed
+            }
+            Action::OpenTicketWorktree => {
+                self.ticket_component.reset();
+                self.focus = Focus::Ticket;
+                self.mode = InputMode::Insert;
+                EventState::Consumed
+            }
+            Action::OpenMetadata => {
+                if let Some(worktree) = self.worktrees_component.selected_worktree_mut() {
+                    let label = match worktree.repository_name() {
+                        Some(repo) => format!("{} / {}", repo, worktree.name()),
+                        None => worktree.name().to_string(),
+                    };
+                    let path = worktree.path().to_string();
+                    let metadata = self
+                        .worktrees_component
+                        .metadata(&path)
+                        .cloned()
+                        .unwrap_or_default();
+                    self.metadata_component = MetadataComponent::new(label, &metadata);
+                    self.focus = Focus::Metadata;
+                    self.mode = InputMode::Insert;
+                }
+                EventState::Consumed
+            }
             Action::Delete | Action::ForceDelete => {
-                match self.worktrees_component.delete_selected_worktree() {
-                    Ok(()) => self.worktrees_component.last_error = None,
-                    Err(e) => self.worktrees_component.last_error = Some(format!("{:#}", e)),
+                let operation = if action == Action::ForceDelete {
+                    BatchOperation::ForceDelete
+                } else {
+                    BatchOperation::Delete
+                };
+                let targets = self.worktrees_component.target_paths();
+                if targets.len() > 1 {
+                    self.confirm_batch(operation, targets);
+                } else {
+                    self.run_batch(operation, targets);
                 }
                 EventState::Consumed
             }
-            Action::DeleteWithConfirmation => {
+            Action::Prune => {
+                let targets = self.worktrees_component.prunable_paths();
+                if targets.is_empty() {
+                    self.worktrees_component.last_error =
+                        Some("No unpinned worktree has a gone remote branch".to_string());
+                } else {
+                    self.confirm_batch(BatchOperation::Prune, targets);
+                }
+                EventState::Consumed
+            }
+            Action::Fetch | Action::Pull | Action::OpenInEditor | Action::OpenTerminal => {
+                let operation = match action {
+                    Action::Fetch => BatchOperation::Fetch,
+                    Action::Pull => BatchOperation::Pull,
+                    Action::OpenInEditor => BatchOperation::OpenInEditor,
+                    _ => BatchOperation::OpenTerminal,
+                };
+                let targets = self.worktrees_component.target_paths();
+                if targets.len() > 1 {
+                    self.confirm_batch(operation, targets);
+                } else {
+                    self.run_batch(operation, targets);
+                }
+                EventState::Consumed
+            }
+            Action::RunCommand => {
+                if self.command_run.is_some() {
+                    self.focus = Focus::CommandOutput;
+                } else {
+                    let targets = self.worktrees_component.target_paths();
+                    if !targets.is_empty() {
+                        self.run_command_component = RunCommandComponent::new(targets);
+                        self.focus = Focus::RunCommand;
+                        self.mode = InputMode::Insert;
+                    }
+                }
+                EventState::Consumed
+            }
+            Action::ClosePopup => {
+                self.worktrees_component.clear_marks();
+                EventState::Consumed
+            }
+            Action::TogglePin => self.toggle_pin(),
+            Action::CopyPath => {
                 if let Some(path) = self.worktrees_component.selected_worktree_path() {
+                    self.worktrees_component.last_error = Some(match clipboard::copy(&path) {
+                        Ok(method) => format!("Copied {} ({})", path, method),
+                        Err(e) => format!("Could not copy the path: {:#}", e),
+                    });
+                }
+                EventState::Consumed
+            }
+            Action::ToggleLog => {
+                self.log_component.visible = !self.log_component.visible;
+                EventState::Consumed
+            }
+            Action::ToggleGrouped => {
+                let grouped = !self.worktrees_component.is_grouped();
+                self.worktrees_component.set_grouped(grouped);
+                self.history.set_grouped(grouped);
+                if let Err(e) = self.history.save() {
+                    debug!("Could not save the worktrees view: {:#}", e);
+                }
+                EventState::Consumed
+            }
+            Action::CycleSort => {
+                let sort_mode = self.worktrees_component.sort_mode().next();
+                self.worktrees_component.set_sort_mode(sort_mode);
+                self.history.set_sort_mode(sort_mode);
+                if let Err(e) = self.history.save() {
+                    debug!("Could not save the sort mode: {:#}", e);
+                }
+                EventState::Consumed
+            }
+            Action::DeleteWithConfirmation => {
+                let targets = self.worktrees_component.target_paths();
+                if targets.len() > 1 {
+                    self.confirm_batch(BatchOperation::Delete, targets);
+                } else if let Some(path) = targets.into_iter().next() {
                     self.confirm_component = ConfirmComponent::new(
                         "Delete Worktree".to_string(),
                         "Delete this worktree?".to_string(),
-                        path,
+                        path.clone(),
                     );
                     self.confirm_action = ConfirmAction::DeleteWorktree;
+                    // The worktree named in the popup, whatever the cursor is
+                    // on once it is confirmed.
+                    self.pending_batch = vec![path];
                     self.focus = Focus::Confirm;
                 }
                 EventState::Consumed
@@ -237,10 +1049,28 @@ impl App {
             }
             _ => {
                 let result = self.worktrees_component.handle_action(action);
-                if result == EventState::Exit {
-                    self.selected_path = self.worktrees_component.selected_worktree_path();
+                if result != EventState::Exit {
+                    return result;
+                }
+                let Some(path) = self.worktrees_component.selected_worktree_path() else {
+                    return result;
+                };
+                self.history.record(&path, history::now());
+                if let Err(e) = self.history.save() {
+                    debug!("Could not save the worktree history: {:#}", e);
+                }
+                match self.config.sessions.multiplexer {
+                    Some(multiplexer) => {
+                        if let Err(e) = self.open_session(multiplexer, &path) {
+                            self.worktrees_component.last_error = Some(format!("{:#}", e));
+                        }
+                        EventState::Consumed
+                    }
+                    None => {
+                        self.selected_path = Some(path);
+                        result
+                    }
                 }
-                result
             }
         }
     }
@@ -256,17 +1086,40 @@ impl App {
                 EventState::Consumed
             }
             Action::Select => {
-                let repo_name = self
-                    .repositories_component
-                    .selected_repository()
-                    .map(|r| r.name())
-                    .unwrap_or_default();
-                self.create_worktree = CreateWorktreeComponent::new(repo_name);
+                let selected_repository = self.repositories_component.selected_repository();
+                let untracked = selected_repository
+                    .map(|repo| repo.untracked_files(&self.config.untracked(&repo.name())))
+                    .transpose()
+                    .unwrap_or_else(|e| {
+                        self.worktrees_component.last_error = Some(format!("{:#}", e));
+                        None
+                    });
+                let create_worktree = match (&self.pending_ticket, selected_repository) {
+                    (Some(ticket), Some(repo)) => {
+                        let mut create_worktree = CreateWorktreeComponent::new_with_branch(
+                            repo.name(),
+                            ticket.branch.clone(),
+                            None,
+                        );
+                        create_worktree.base_branch_hint =
+                            Some(repo.resolve_base_branch(&ticket.branch));
+                        create_worktree
+                    }
+                    (_, repo) => {
+                        let repo_name = repo.map(|r| r.name()).unwrap_or_default();
+                        let templates = self.config.branch_templates(&repo_name);
+                        CreateWorktreeComponent::new(repo_name)
+                            .with_templates(templates, &self.config.branch)
+                    }
+                };
+                self.create_worktree =
+                    create_worktree.with_untracked(untracked.unwrap_or_default());
                 self.focus = Focus::CreateWorktree;
                 self.mode = InputMode::Insert;
                 EventState::Consumed
             }
             Action::ClosePopup => {
+                self.pending_ticket = None;
                 self.focus = Focus::Worktrees;
                 self.mode = InputMode::Normal;
                 EventState::Consumed
@@ -299,15 +1152,24 @@ impl App {
             Action::Quit => EventState::Exit,
             Action::Select => {
                 if !self.create_worktree.new_worktree_name.is_empty() {
+                    let branch = self.create_worktree.branch_name();
+                    let directory = self.config.branch.directory_name(&branch);
                     if let Some(selected_repository) =
                         self.repositories_component.selected_repository()
                     {
                         match selected_repository.create_new_worktree(
-                            &self.create_worktree.new_worktree_name,
+                            &branch,
+                            &directory,
                             &self.args.worktrees_dir,
+                            self.create_worktree.untracked_files(),
                         ) {
-                            Ok(created_worktree) => {
+                            Ok((created_worktree, untracked_errors)) => {
                                 self.worktrees_component.last_error = None;
+                                self.report_untracked_errors(untracked_errors);
+                                if let Some(ticket) = self.pending_ticket.take() {
+                                    self.attach_ticket(&created_worktree, ticket.issue.url);
+                                }
+                                self.run_post_create_hooks(&created_worktree);
                                 self.worktrees_component.add(created_worktree);
                             }
                             Err(e) => {
@@ -321,6 +1183,7 @@ impl App {
                 EventState::Consumed
             }
             Action::ClosePopup | Action::ExitInsertMode => {
+                self.pending_ticket = None;
                 self.focus = Focus::Worktrees;
                 self.mode = InputMode::Normal;
                 EventState::Consumed
@@ -328,14 +1191,15 @@ impl App {
             _ => {
                 let result = self.create_worktree.handle_action(action);
                 if result == EventState::Consumed {
-                    let name = self.create_worktree.new_worktree_name.clone();
-                    self.create_worktree.base_branch_hint = if name.is_empty() {
-                        None
-                    } else {
-                        self.repositories_component
-                            .selected_repository()
-                            .map(|r| r.resolve_base_branch(&name))
-                    };
+                    self.create_worktree.base_branch_hint =
+                        if self.create_worktree.new_worktree_name.is_empty() {
+                            None
+                        } else {
+                            let branch = self.create_worktree.branch_name();
+                            self.repositories_component
+                                .selected_repository()
+                                .map(|r| r.resolve_base_branch(&branch))
+                        };
                 }
                 result
             }
@@ -347,16 +1211,21 @@ impl App {
             Action::Quit => EventState::Exit,
             Action::Select => match self.confirm_action {
                 ConfirmAction::DeleteWorktree => {
-                    match self.worktrees_component.delete_selected_worktree() {
-                        Ok(()) => self.worktrees_component.last_error = None,
-                        Err(e) => self.worktrees_component.last_error = Some(format!("{:#}", e)),
-                    }
                     self.focus = Focus::Worktrees;
+                    let paths = std::mem::take(&mut self.pending_batch);
+                    self.run_batch(BatchOperation::Delete, paths);
                     EventState::Consumed
                 }
                 ConfirmAction::CloneRepo => self.handle_clone_confirmed(),
+                ConfirmAction::Batch(operation) => {
+                    self.focus = Focus::Worktrees;
+                    let paths = std::mem::take(&mut self.pending_batch);
+                    self.run_batch(operation, paths);
+                    EventState::Consumed
+                }
             },
             Action::ClosePopup | Action::ExitInsertMode => {
+                self.pending_batch.clear();
                 self.pending_pr = None;
                 self.focus = Focus::Worktrees;
                 EventState::Consumed
@@ -391,7 +1260,7 @@ impl App {
             }
         };
 
-        let pr_info = match github::fetch_pr_info(&pr_url) {
+        let pr_info = match self.github.fetch_pr_info(&pr_url) {
             Ok(info) => info,
             Err(e) => {
                 self.pr_worktree_component.set_error(format!("{:#}", e));
@@ -411,7 +1280,7 @@ impl App {
             self.confirm_component = ConfirmComponent::new(
                 "Clone Repository".to_string(),
                 format!("Repository '{}' not found. Clone from GitHub?", pr_url.repo),
-                format!("git@github.com:{}/{}.git", pr_url.owner, pr_url.repo),
+                github::clone_url(&pr_url.owner, &pr_url.repo, self.args.clone_protocol),
             );
             self.confirm_action = ConfirmAction::CloneRepo;
             self.focus = Focus::Confirm;
@@ -445,10 +1314,39 @@ impl App {
             }
         };
 
-        if let Err(e) = github::clone_repository(&pr_url.owner, &pr_url.repo, &repos_dir) {
+        self.clone_progress_component =
+            CloneProgressComponent::new(format!("{}/{}", pr_url.owner, pr_url.repo));
+        self.pending_clone = Some(PendingClone {
+            events: github::clone_repository(
+                &pr_url.owner,
+                &pr_url.repo,
+                &repos_dir,
+                self.args.clone_protocol,
+                self.args.partial_clone,
+            ),
+            repos_dir,
+            pr_url,
+            pr_info,
+            auto,
+        });
+        self.focus = Focus::Cloning;
+        self.mode = InputMode::Normal;
+        EventState::Consumed
+    }
+
+    fn handle_clone_finished(&mut self, pending: PendingClone, result: color_eyre::Result<()>) {
+        let PendingClone {
+            repos_dir,
+            pr_url,
+            pr_info,
+            auto,
+            ..
+        } = pending;
+
+        if let Err(e) = result {
             self.worktrees_component.last_error = Some(format!("{:#}", e));
             self.focus = Focus::Worktrees;
-            return EventState::Consumed;
+            return;
         }
 
         let repo_path = format!("{}/{}", repos_dir, pr_url.repo);
@@ -462,11 +1360,606 @@ impl App {
                 self.worktrees_component.last_error =
                     Some(format!("Cloned but failed to load repo: {:#}", e));
                 self.focus = Focus::Worktrees;
+                return;
+            }
+        }
+
+        self.open_worktree_for_pr(pr_info, auto);
+    }
+
+    fn handle_cloning_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::Quit => EventState::Exit,
+            Action::ShowHelp => {
+                self.previous_focus = self.focus;
+                self.help_component =
+                    HelpComponent::new(Self::help_bindings_for(self.focus, self.mode));
+                self.focus = Focus::Help;
+                EventState::Consumed
+            }
+            _ => EventState::Consumed,
+        }
+    }
+
+    fn handle_create_pr_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::Quit => EventState::Exit,
+            Action::ClosePopup | Action::ExitInsertMode => {
+                self.focus = Focus::Worktrees;
+                self.mode = InputMode::Normal;
+                EventState::Consumed
+            }
+            Action::Submit => self.handle_create_pr_submission(),
+            Action::Select if !self.create_pr_component.is_body_focused() => {
+                self.handle_create_pr_submission()
+            }
+            _ => self.create_pr_component.handle_action(action),
+        }
+    }
+
+    /// Pushes the selected worktree's branch and opens the PR on GitHub.
+    fn handle_create_pr_submission(&mut self) -> EventState {
+        if self.create_pr_component.title().is_empty() {
+            self.create_pr_component
+                .set_error("The PR title cannot be empty".to_string());
+            return EventState::Consumed;
+        }
+
+        let Some(worktree) = self.worktrees_component.selected_worktree_mut() else {
+            self.focus = Focus::Worktrees;
+            self.mode = InputMode::Normal;
+            return EventState::Consumed;
+        };
+
+        let Some((owner, repo)) = worktree
+            .origin_url()
+            .and_then(|url| github::parse_remote_url(&url))
+        else {
+            self.create_pr_component
+                .set_error("Remote 'origin' is not a GitHub repository".to_string());
+            return EventState::Consumed;
+        };
+
+        let branch = match worktree.push_to_origin() {
+            Ok(branch) => branch,
+            Err(e) => {
+                self.create_pr_component.set_error(format!("{:#}", e));
+                return EventState::Consumed;
+            }
+        };
+
+        let new_pr = self.create_pr_component.new_pull_request(branch);
+        match self.github.create_pull_request(&owner, &repo, &new_pr) {
+            Ok(url) => {
+                self.worktrees_component.last_error = Some(format!("Created {}", url));
+                // Pick the new PR up on the next tick instead of waiting for the interval.
+                self.last_pr_status_refresh = None;
+                self.focus = Focus::Worktrees;
+                self.mode = InputMode::Normal;
+            }
+            Err(e) => self.create_pr_component.set_error(format!("{:#}", e)),
+        }
+        EventState::Consumed
+    }
+
+    fn handle_ticket_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::Quit => EventState::Exit,
+            Action::ClosePopup | Action::ExitInsertMode => {
+                self.ticket_component.reset();
+                self.focus = Focus::Worktrees;
+                self.mode = InputMode::Normal;
+                EventState::Consumed
+            }
+            Action::Select => self.handle_ticket_submission(),
+            _ => self.ticket_component.handle_action(action),
+        }
+    }
+
+    /// Fetches the issue, proposes a branch name for it and lets the user pick
+    /// the repository to create the worktree in.
+    fn handle_ticket_submission(&mut self) -> EventState {
+        let Some(tracker_config) = &self.config.tracker else {
+            let config_path = config::config_path()
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|_| "config.toml".to_string());
+            self.ticket_component.set_error(format!(
+                "No issue tracker configured — add a [tracker] section to {}",
+                config_path
+            ));
+            return EventState::Consumed;
+        };
+
+        let issue = match tracker::parse_issue_key(self.ticket_component.current_input())
+            .and_then(|key| tracker::fetch_issue(tracker_config, &key))
+        {
+            Ok(issue) => issue,
+            Err(e) => {
+                self.ticket_component.set_error(format!("{:#}", e));
                 return EventState::Consumed;
             }
+        };
+
+        let branch = tracker::branch_name(&tracker_config.branch_template, &issue);
+        if !is_valid_branch_name(&branch) {
+            self.ticket_component.set_error(format!(
+                "'{}' is not a valid branch name — check branch_template",
+                branch
+            ));
+            return EventState::Consumed;
+        }
+
+        self.ticket_component.reset();
+        self.pending_ticket = Some(PendingTicket { issue, branch });
+        self.focus = Focus::Repositories;
+        self.mode = InputMode::Normal;
+        EventState::Consumed
+    }
+
+    /// Records the ticket link in the metadata of a newly created worktree.
+    fn attach_ticket(&mut self, worktree: &git::Worktree, url: String) {
+        let Some(repo) = worktree.repository_name() else {
+            return;
+        };
+        let mut metadata = self
+            .metadata_store
+            .get(&repo, worktree.name())
+            .cloned()
+            .unwrap_or_default();
+        if !metadata.tickets.contains(&url) {
+            metadata.tickets.push(url);
+        }
+        self.metadata_store
+            .set(&repo, worktree.name(), metadata.clone());
+        self.worktrees_component
+            .set_metadata(worktree.path().to_string(), Some(metadata));
+        if let Err(e) = self.metadata_store.save() {
+            self.worktrees_component.last_error = Some(format!("{:#}", e));
+        }
+    }
+
+    fn handle_metadata_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::Quit => EventState::Exit,
+            Action::ClosePopup | Action::ExitInsertMode => {
+                self.focus = Focus::Worktrees;
+                self.mode = InputMode::Normal;
+                EventState::Consumed
+            }
+            Action::Submit => self.handle_metadata_submission(),
+            Action::Select if !self.metadata_component.is_notes_focused() => {
+                self.handle_metadata_submission()
+            }
+            _ => self.metadata_component.handle_action(action),
         }
+    }
+
+    fn handle_metadata_submission(&mut self) -> EventState {
+        self.focus = Focus::Worktrees;
+        self.mode = InputMode::Normal;
+
+        let Some(worktree) = self.worktrees_component.selected_worktree_mut() else {
+            return EventState::Consumed;
+        };
+        let Some(repo) = worktree.repository_name() else {
+            self.worktrees_component.last_error =
+                Some("Could not find the repository of the worktree".to_string());
+            return EventState::Consumed;
+        };
+        let name = worktree.name().to_string();
+        let path = worktree.path().to_string();
+
+        let metadata = self.metadata_component.metadata();
+        self.metadata_store.set(&repo, &name, metadata.clone());
+        self.worktrees_component
+            .set_metadata(path, Some(metadata).filter(|m| !m.is_empty()));
+        self.worktrees_component.last_error =
+            self.metadata_store.save().err().map(|e| format!("{:#}", e));
+        EventState::Consumed
+    }
+
+    fn toggle_pin(&mut self) -> EventState {
+        let Some(worktree) = self.worktrees_component.selected_worktree_mut() else {
+            return EventState::Consumed;
+        };
+        let Some(repo) = worktree.repository_name() else {
+            self.worktrees_component.last_error =
+                Some("Could not find the repository of the worktree".to_string());
+            return EventState::Consumed;
+        };
+        let name = worktree.name().to_string();
+        let path = worktree.path().to_string();
 
-        self.open_worktree_for_pr(pr_info, auto)
+        let mut metadata = self
+            .worktrees_component
+            .metadata(&path)
+            .cloned()
+            .unwrap_or_default();
+        metadata.pinned = !metadata.pinned;
+        self.metadata_store.set(&repo, &name, metadata.clone());
+        self.worktrees_component
+            .set_metadata(path, Some(metadata).filter(|m| !m.is_empty()));
+        self.worktrees_component.last_error =
+            self.metadata_store.save().err().map(|e| format!("{:#}", e));
+        EventState::Consumed
+    }
+
+    fn confirm_batch(&mut self, operation: BatchOperation, paths: Vec<String>) {
+        let items = paths
+            .iter()
+            .map(|path| self.worktrees_component.label(path))
+            .collect();
+        let count = match paths.len() {
+            1 => "this worktree".to_string(),
+            n => format!("these {} worktrees", n),
+        };
+        self.confirm_component = ConfirmComponent::new(
+            operation.title().to_string(),
+            format!("{} {}?", operation.verb(), count),
+            operation.detail().to_string(),
+        )
+        .with_items(items);
+        self.confirm_action = ConfirmAction::Batch(operation);
+        self.pending_batch = paths;
+        self.focus = Focus::Confirm;
+    }
+
+    /// Applies `operation` to each worktree in turn, carrying on past failures.
+    /// A single worktree reports a failure in the status line; several get a
+    /// summary of what happened to each.
+    fn run_batch(&mut self, operation: BatchOperation, paths: Vec<String>) {
+        match operation {
+            BatchOperation::Fetch | BatchOperation::Pull => {
+                return self.start_batch_run(operation, paths);
+            }
+            BatchOperation::Delete | BatchOperation::ForceDelete | BatchOperation::Prune => {
+                return self.start_delete_run(operation, paths);
+            }
+            BatchOperation::OpenInEditor | BatchOperation::OpenTerminal => {}
+        }
+        let outcomes: Vec<Outcome> = paths
+            .iter()
+            .map(|path| {
+                let label = self.worktrees_component.label(path);
+                let result = self.open(operation, path).map_err(|e| format!("{:#}", e));
+                (label, result)
+            })
+            .collect();
+        self.show_outcomes(operation, outcomes);
+    }
+
+    fn show_outcomes(&mut self, operation: BatchOperation, outcomes: Vec<Outcome>) {
+        match outcomes.as_slice() {
+            [] => {}
+            [(_, result)] => self.worktrees_component.last_error = result.clone().err(),
+            _ => {
+                self.worktrees_component.last_error = None;
+                self.summary_component =
+                    SummaryComponent::new(operation.title().to_string(), outcomes);
+                self.focus = Focus::Summary;
+            }
+        }
+    }
+
+    /// Opens the worktree at `path` in the editor or a new terminal.
+    fn open(&mut self, operation: BatchOperation, path: &str) -> eyre::Result<String> {
+        let launch = if operation == BatchOperation::OpenInEditor {
+            self.config.open.editor(path)?
+        } else {
+            self.config.open.terminal(path)?
+        };
+        if launch.foreground {
+            self.foreground_commands.push_back(launch.command);
+        } else {
+            launch.spawn()?;
+        }
+        Ok("opened".to_string())
+    }
+
+    /// Fetches or pulls the worktrees at `paths` one after another on a
+    /// background thread, as it takes a round trip to the remote each;
+    /// `poll_batch_run` shows the outcomes once all are done.
+    fn start_batch_run(&mut self, operation: BatchOperation, paths: Vec<String>) {
+        if self.batch_run.is_some() {
+            self.worktrees_component.last_error =
+                Some("Wait for the running fetch or pull to finish".to_string());
+            return;
+        }
+        if paths.is_empty() {
+            return;
+        }
+        let targets: Vec<_> = paths
+            .into_iter()
+            .map(|path| {
+                let label = self.worktrees_component.label(&path);
+                let repository = self.worktrees_component.repository_of(&path);
+                (path, label, repository)
+            })
+            .collect();
+        self.worktrees_component.last_error = Some(format!(
+            "{} {} worktree(s)…",
+            operation.progress(),
+            targets.len()
+        ));
+
+        let (sender, events) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut fetched_repositories = HashSet::new();
+            for (path, label, repository) in targets {
+                let result = match operation {
+                    BatchOperation::Pull => git::pull_worktree(&path),
+                    // Worktrees of a repository share its remote-tracking branches.
+                    _ if fetched_repositories.contains(&repository) => {
+                        Ok("fetched with another worktree".to_string())
+                    }
+                    _ => git::fetch_worktree(&path).map(|()| {
+                        fetched_repositories.insert(repository);
+                        "fetched".to_string()
+                    }),
+                };
+                let outcome = (label, result.map_err(|e| format!("{:#}", e)));
+                if sender.send((path, outcome)).is_err() {
+                    return;
+                }
+            }
+        });
+        self.batch_run = Some(BatchRun {
+            operation,
+            events,
+            outcomes: vec![],
+        });
+    }
+
+    fn poll_batch_run(&mut self) {
+        let Some(run) = &mut self.batch_run else {
+            return;
+        };
+        loop {
+            match run.events.try_recv() {
+                Ok((path, outcome)) => {
+                    if let Some(worktree) = self.worktrees_component.worktree_mut(&path) {
+                        worktree.refresh_remote_status();
+                    }
+                    run.outcomes.push(outcome);
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    let run = self.batch_run.take().expect("checked above");
+                    self.worktrees_component.last_error = None;
+                    self.show_outcomes(run.operation, run.outcomes);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Deletes the worktrees at `paths` one after another, each between its
+    /// `pre_delete` and `post_delete` hooks. The hooks run in the background;
+    /// `poll_delete_run` carries on once they are done.
+    fn start_delete_run(&mut self, operation: BatchOperation, paths: Vec<String>) {
+        if self.delete_run.is_some() {
+            self.worktrees_component.last_error =
+                Some("Wait for the running delete to finish".to_string());
+            return;
+        }
+        self.delete_run = Some(DeleteRun {
+            operation,
+            queue: paths.into(),
+            current: None,
+            outcomes: vec![],
+        });
+        self.advance_delete_run();
+    }
+
+    /// Deletes the next worktrees until one has hooks to wait for, or shows
+    /// the outcomes once all are done.
+    fn advance_delete_run(&mut self) {
+        let Some(mut run) = self.delete_run.take() else {
+            return;
+        };
+        let force = run.operation == BatchOperation::ForceDelete;
+        while run.current.is_none() {
+            let Some(path) = run.queue.pop_front() else {
+                self.worktrees_component.clear_marks();
+                if let Err(e) = self.history.save() {
+                    debug!("Could not save the worktree history: {:#}", e);
+                }
+                self.remove_orphaned_metadata();
+                self.show_outcomes(run.operation, run.outcomes);
+                return;
+            };
+            let label = self.worktrees_component.label(&path);
+            match self.start_delete(&path, &label, force) {
+                Ok(Some(hooks)) => run.current = Some(hooks),
+                Ok(None) => run.outcomes.push((label, Ok("deleted".to_string()))),
+                Err(e) => run.outcomes.push((label, Err(format!("{:#}", e)))),
+            }
+        }
+        self.delete_run = Some(run);
+    }
+
+    /// Starts the `pre_delete` hooks of the worktree at `path`, or deletes it
+    /// right away when it has none. Returns the hooks left to wait for.
+    fn start_delete(
+        &mut self,
+        path: &str,
+        label: &str,
+        force: bool,
+    ) -> eyre::Result<Option<DeleteHooks>> {
+        if self.worktrees_component.is_pinned(path) {
+            eyre::bail!("Worktree is pinned — unpin it with f first");
+        }
+        // Gathered up front, as the repository can't be found from a deleted
+        // worktree.
+        let context = self
+            .worktrees_component
+            .worktrees()
+            .iter()
+            .find(|wt| wt.path() == path)
+            .and_then(|wt| HookContext::for_worktree(wt, "pre_delete"));
+        let Some(context) = context else {
+            self.remove_worktree(path)?;
+            return Ok(None);
+        };
+        let hooks = self
+            .config
+            .hooks(&context.repo, Path::new(&context.repo_path))?;
+        let post_delete = hooks.post_delete.unwrap_or_default();
+        let pre_delete = hooks.pre_delete.unwrap_or_default();
+        if pre_delete.is_empty() {
+            self.remove_worktree(path)?;
+            return Ok(self.start_post_delete_hooks(path, label, context, post_delete));
+        }
+        // With force, every hook runs and the worktree is deleted anyway.
+        Ok(Some(self.start_delete_hooks(
+            path,
+            label,
+            context,
+            pre_delete,
+            post_delete,
+            force,
+        )))
+    }
+
+    fn start_post_delete_hooks(
+        &mut self,
+        path: &str,
+        label: &str,
+        context: HookContext,
+        commands: Vec<String>,
+    ) -> Option<DeleteHooks> {
+        if commands.is_empty() {
+            return None;
+        }
+        let context = HookContext {
+            hook: "post_delete",
+            ..context
+        };
+        Some(self.start_delete_hooks(path, label, context, commands, vec![], true))
+    }
+
+    fn start_delete_hooks(
+        &mut self,
+        path: &str,
+        label: &str,
+        context: HookContext,
+        commands: Vec<String>,
+        post_delete: Vec<String>,
+        keep_going: bool,
+    ) -> DeleteHooks {
+        self.log_component.push_info(format!(
+            "Running {} hooks of {} / {}",
+            context.hook, context.repo, context.branch
+        ));
+        self.log_component.visible = true;
+        self.worktrees_component.last_error =
+            Some(format!("Running {} hooks of {}…", context.hook, label));
+        DeleteHooks {
+            path: path.to_string(),
+            label: label.to_string(),
+            events: hooks::run_in_background(commands, context.clone(), keep_going),
+            context,
+            post_delete,
+            errors: vec![],
+        }
+    }
+
+    /// Streams the output of the running delete hooks into the log. Once they
+    /// are done, deletes the worktree after its `pre_delete` hooks passed, or
+    /// failed with force, and moves on.
+    fn poll_delete_run(&mut self) {
+        let Some(hooks) = self
+            .delete_run
+            .as_mut()
+            .and_then(|run| run.current.as_mut())
+        else {
+            return;
+        };
+        loop {
+            match hooks.events.try_recv() {
+                Ok(HookEvent::Failed(error)) => {
+                    self.log_component.push_error(error.clone());
+                    hooks.errors.push(error);
+                }
+                Ok(HookEvent::Started(command)) => self.log_component.push_command(&command),
+                Ok(HookEvent::Output(line)) => self.log_component.push_output(line),
+                Ok(HookEvent::Finished) | Err(TryRecvError::Disconnected) => break,
+                Err(TryRecvError::Empty) => return,
+            }
+        }
+
+        let Some(run) = self.delete_run.as_mut() else {
+            return;
+        };
+        let force = run.operation == BatchOperation::ForceDelete;
+        let Some(DeleteHooks {
+            path,
+            label,
+            context,
+            post_delete,
+            errors,
+            ..
+        }) = run.current.take()
+        else {
+            return;
+        };
+        let result = match (context.hook, errors.first()) {
+            ("pre_delete", Some(error)) if !force => Err(format!(
+                "pre_delete hook failed, the worktree was kept (D deletes anyway): {}",
+                error
+            )),
+            ("pre_delete", _) => match self.remove_worktree(&path) {
+                Ok(()) => {
+                    let next = self.start_post_delete_hooks(&path, &label, context, post_delete);
+                    if let (Some(next), Some(run)) = (next, self.delete_run.as_mut()) {
+                        run.current = Some(next);
+                        return;
+                    }
+                    Ok("deleted".to_string())
+                }
+                Err(e) => Err(format!("{:#}", e)),
+            },
+            // Reported as a failure so it isn't missed, though the worktree is gone.
+            (_, Some(_)) => Err(format!(
+                "Deleted, but a post_delete hook failed: {}",
+                errors.join("; ")
+            )),
+            (_, None) => Ok("deleted".to_string()),
+        };
+        if let Some(run) = self.delete_run.as_mut() {
+            run.outcomes.push((label, result));
+        }
+        self.advance_delete_run();
+    }
+
+    /// Ends the session of the worktree at `path` and deletes it.
+    fn remove_worktree(&mut self, path: &str) -> eyre::Result<()> {
+        if let Some(multiplexer) = self.config.sessions.multiplexer {
+            let name = self.session_name(multiplexer, path);
+            if let Err(e) = multiplexer.kill(&name) {
+                self.log_component
+                    .push_error(format!("Could not end session {}: {:#}", name, e));
+            }
+        }
+        self.worktrees_component.delete_worktree(path)?;
+        self.history.remove(path);
+        Ok(())
+    }
+
+    /// Forgets the metadata of worktrees that no longer exist, whether deleted
+    /// from shanti or with `git worktree remove`.
+    fn remove_orphaned_metadata(&mut self) {
+        let worktrees: Vec<(String, String)> = self
+            .worktrees_component
+            .worktrees()
+            .iter()
+            .filter_map(|wt| Some((wt.repository_name()?, wt.name().to_string())))
+            .collect();
+        self.metadata_store
+            .remove_orphans(&self.repositories_component.repository_names(), &worktrees);
+        if let Err(e) = self.metadata_store.save() {
+            debug!("Could not save worktree metadata: {:#}", e);
+        }
     }
 
     fn handle_select_repos_dir_action(&mut self, action: Action) -> EventState {
@@ -504,9 +1997,20 @@ impl App {
         self.pr_worktree_component.reset();
 
         if auto {
+            let directory = self.config.branch.directory_name(&branch);
             if let Some(repo) = self.repositories_component.selected_repository() {
-                match repo.create_new_worktree(&branch, &self.args.worktrees_dir) {
-                    Ok(worktree) => {
+                let created = repo
+                    .untracked_files(&self.config.untracked(&repo.name()))
+                    .and_then(|untracked| {
+                        repo.create_new_worktree(
+                            &branch,
+                            &directory,
+                            &self.args.worktrees_dir,
+                            &untracked,
+                        )
+                    });
+                match created {
+                    Ok((worktree, untracked_errors)) => {
                         self.worktrees_component.last_error = if pr_info.is_merged {
                             Some(
                                 "Warning: PR is merged, branch may be deleted on remote"
@@ -515,6 +2019,8 @@ impl App {
                         } else {
                             None
                         };
+                        self.report_untracked_errors(untracked_errors);
+                        self.run_post_create_hooks(&worktree);
                         self.worktrees_component.add(worktree);
                     }
                     Err(e) => {
@@ -531,15 +2037,21 @@ impl App {
                 None
             };
 
-            let (repo_name, base_branch_hint) =
+            let (repo_name, base_branch_hint, untracked) =
                 if let Some(r) = self.repositories_component.selected_repository() {
-                    (r.name(), Some(r.resolve_base_branch(&branch)))
+                    let untracked = r.untracked_files(&self.config.untracked(&r.name()));
+                    (r.name(), Some(r.resolve_base_branch(&branch)), untracked)
                 } else {
-                    (String::new(), None)
+                    (String::new(), None, Ok(Vec::new()))
                 };
 
+            let untracked = untracked.unwrap_or_else(|e| {
+                self.worktrees_component.last_error = Some(format!("{:#}", e));
+                Vec::new()
+            });
             self.create_worktree =
-                CreateWorktreeComponent::new_with_branch(repo_name, branch, warning);
+                CreateWorktreeComponent::new_with_branch(repo_name, branch, warning)
+                    .with_untracked(untracked);
             self.create_worktree.base_branch_hint = base_branch_hint;
             self.focus = Focus::CreateWorktree;
             self.mode = InputMode::Insert;
@@ -548,6 +2060,7 @@ impl App {
     }
 
     fn help_bindings_for(focus: Focus, mode: InputMode) -> Vec<HelpEntry> {
+        let glyphs = theme::current().glyphs;
         match (focus, mode) {
             (Focus::Worktrees, InputMode::Normal) => vec![
                 HelpEntry::Section("Keybindings"),
@@ -557,20 +2070,51 @@ impl App {
                 HelpEntry::Binding("G / End", "Go to last"),
                 HelpEntry::Binding("i / /", "Enter filter mode"),
                 HelpEntry::Binding("Tab", "Toggle filter / list"),
-                HelpEntry::Binding("n", "New worktree (pick repo)"),
+                HelpEntry::Binding("n", "New worktree (pick repo, or the selected one)"),
                 HelpEntry::Binding("p", "New worktree from PR URL"),
                 HelpEntry::Binding("P", "New worktree from PR URL (auto-clone)"),
+                HelpEntry::Binding("t", "New worktree from ticket"),
+                HelpEntry::Binding("c", "Push branch & create PR"),
+                HelpEntry::Binding("m", "Edit tags, tickets & notes"),
+                HelpEntry::Binding("f", "Pin / unpin"),
+                HelpEntry::Binding("v", "Toggle grouping by repository"),
+                HelpEntry::Binding("h / l", "Collapse / expand repository"),
+                HelpEntry::Binding("s", "Cycle sort: a-z, recent, frecent, last commit"),
+                HelpEntry::Binding("Space", "Mark / unmark and move down"),
+                HelpEntry::Binding("V", "Start / end marking a range"),
+                HelpEntry::Binding("A", "Mark / unmark all filtered"),
+                HelpEntry::Binding("Esc", "Clear marks"),
+                HelpEntry::Blank,
+                HelpEntry::Section("On marked worktrees, or the selected one"),
                 HelpEntry::Binding("d", "Delete with confirmation"),
-                HelpEntry::Binding("D", "Force delete"),
-                HelpEntry::Binding("Enter", "Copy path to clipboard & exit"),
+                HelpEntry::Binding("D", "Force delete, past failing pre_delete hooks"),
+                HelpEntry::Binding("X", "Prune worktrees with gone remote branches"),
+                HelpEntry::Binding("F", "Fetch with prune"),
+                HelpEntry::Binding("u", "Pull (fast-forward only)"),
+                HelpEntry::Binding("e", "Open in editor"),
+                HelpEntry::Binding("T", "Open in a new terminal"),
+                HelpEntry::Binding("!", "Run a shell command in each"),
+                HelpEntry::Blank,
+                HelpEntry::Binding("L", "Show / hide the hooks log"),
+                HelpEntry::Binding("y", "Copy path to clipboard"),
+                HelpEntry::Binding("Enter", "Print path & exit, or open its session"),
                 HelpEntry::Binding("?", "Show this help"),
                 HelpEntry::Binding("q / Ctrl+C", "Quit"),
                 HelpEntry::Blank,
                 HelpEntry::Section("Worktree State"),
-                HelpEntry::Binding("✔", "Remote branch exists"),
-                HelpEntry::Binding("✘", "Merged / deleted remotely"),
-                HelpEntry::Binding("⬆", "Never pushed to remote"),
-                HelpEntry::Binding("*", "Dirty working tree"),
+                HelpEntry::Binding(glyphs.pushed, "Remote branch exists"),
+                HelpEntry::Binding(glyphs.gone, "Merged / deleted remotely"),
+                HelpEntry::Binding(glyphs.never_pushed, "Never pushed to remote"),
+                HelpEntry::Binding(glyphs.dirty, "Dirty working tree"),
+                HelpEntry::Binding(glyphs.pinned, "Pinned"),
+                HelpEntry::Binding(glyphs.marked, "Marked"),
+                HelpEntry::Binding(glyphs.session, "Live tmux / zellij session"),
+                HelpEntry::Blank,
+                HelpEntry::Section("PR Status"),
+                HelpEntry::Binding("#N", "Latest PR opened from the branch"),
+                HelpEntry::Binding("open", "Open / draft / merged / closed"),
+                HelpEntry::Binding("approved", "Review decision"),
+                HelpEntry::Binding("ci", "CI passing, pending or failing, as its glyph shows"),
             ],
             (Focus::Worktrees, InputMode::Insert) => vec![
                 HelpEntry::Section("Keybindings"),
@@ -579,7 +2123,7 @@ impl App {
                 HelpEntry::Binding("↑ / Ctrl+K / Ctrl+P", "Move up in list"),
                 HelpEntry::Binding("↓ / Ctrl+J / Ctrl+N", "Move down in list"),
                 HelpEntry::Binding("Backspace", "Delete filter character"),
-                HelpEntry::Binding("Enter", "Copy path to clipboard & exit"),
+                HelpEntry::Binding("Enter", "Print path & exit, or open its session"),
                 HelpEntry::Binding("Ctrl+C", "Quit"),
             ],
             (Focus::Repositories, InputMode::Normal) => vec![
@@ -625,6 +2169,42 @@ impl App {
                 HelpEntry::Binding("Backspace", "Delete character"),
                 HelpEntry::Binding("Ctrl+C", "Quit"),
             ],
+            (Focus::CreatePr, _) => vec![
+                HelpEntry::Section("Keybindings"),
+                HelpEntry::Binding("Ctrl+S", "Push branch & create PR"),
+                HelpEntry::Binding("Enter", "Create PR (new line in body)"),
+                HelpEntry::Binding("Tab", "Next field"),
+                HelpEntry::Binding("Ctrl+D", "Toggle draft"),
+                HelpEntry::Binding("Backspace", "Delete character"),
+                HelpEntry::Binding("Esc", "Cancel"),
+                HelpEntry::Binding("Ctrl+C", "Quit"),
+            ],
+            (Focus::Ticket, _) => vec![
+                HelpEntry::Section("Keybindings"),
+                HelpEntry::Binding("Enter", "Fetch issue and pick repository"),
+                HelpEntry::Binding("Esc", "Cancel"),
+                HelpEntry::Binding("Backspace", "Delete character"),
+                HelpEntry::Binding("Ctrl+C", "Quit"),
+            ],
+            (Focus::Metadata, _) => vec![
+                HelpEntry::Section("Keybindings"),
+                HelpEntry::Binding("Ctrl+S", "Save"),
+                HelpEntry::Binding("Enter", "Save (new line in notes)"),
+                HelpEntry::Binding("Tab", "Next field"),
+                HelpEntry::Binding("Backspace", "Delete character"),
+                HelpEntry::Binding("Esc", "Cancel"),
+                HelpEntry::Binding("Ctrl+C", "Quit"),
+            ],
+            (Focus::Summary, _) => vec![
+                HelpEntry::Section("Keybindings"),
+                HelpEntry::Binding("j / k", "Scroll"),
+                HelpEntry::Binding("Esc / Enter", "Close"),
+                HelpEntry::Binding("q / Ctrl+C", "Quit"),
+            ],
+            (Focus::Cloning, _) => vec![
+                HelpEntry::Section("Keybindings"),
+                HelpEntry::Binding("q / Ctrl+C", "Quit"),
+            ],
             (Focus::SelectReposDir, _) => vec![
                 HelpEntry::Section("Keybindings"),
                 HelpEntry::Binding("j / ↓", "Move down"),
@@ -639,6 +2219,64 @@ impl App {
         }
     }
 
+    /// The prompt for a command to run in the marked worktrees, then its
+    /// output. Closing the output leaves the command running; `!` shows it
+    /// again.
+    fn handle_run_command_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::Quit => EventState::Exit,
+            Action::ClosePopup | Action::ExitInsertMode => {
+                self.focus = Focus::Worktrees;
+                self.mode = InputMode::Normal;
+                EventState::Consumed
+            }
+            Action::Select if matches!(self.focus, Focus::RunCommand) => {
+                self.mode = InputMode::Normal;
+                let command = self.run_command_component.input().to_string();
+                if command.is_empty() {
+                    self.focus = Focus::Worktrees;
+                    return EventState::Consumed;
+                }
+                let targets: Vec<HookContext> = self
+                    .run_command_component
+                    .paths()
+                    .iter()
+                    .filter_map(|path| {
+                        let worktree = self
+                            .worktrees_component
+                            .worktrees()
+                            .iter()
+                            .find(|wt| wt.path() == path)?;
+                        HookContext::for_worktree(worktree, "exec")
+                    })
+                    .collect();
+                let labels: Vec<String> = targets.iter().map(exec::label).collect();
+                self.run_command_component.start(&labels);
+                // One worktree at a time, as worktrees of a repository share
+                // its refs, which e.g. `git pull` locks.
+                self.command_run = Some(exec::run_in_background(command, targets, 1));
+                self.focus = Focus::CommandOutput;
+                EventState::Consumed
+            }
+            Action::Select => {
+                self.focus = Focus::Worktrees;
+                EventState::Consumed
+            }
+            _ => self.run_command_component.handle_action(action),
+        }
+    }
+
+    fn handle_summary_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::Quit => EventState::Exit,
+            Action::ClosePopup | Action::ExitInsertMode | Action::Select => {
+                self.focus = Focus::Worktrees;
+                EventState::Consumed
+            }
+            _ => self.summary_component.handle_action(action),
+        }
+    }
+
     fn handle_help_action(&mut self, action: Action) -> EventState {
         match action {
             Action::Quit => EventState::Exit,
@@ -666,9 +2304,3 @@ impl App {
         area
     }
 }
-
-impl Default for App {
-    fn default() -> Self {
-        Self::new()
-    }
-}
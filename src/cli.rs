@@ -1,14 +1,29 @@
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueHint};
+use clap_complete::ArgValueCompleter;
+use color_eyre::eyre::{self, eyre, WrapErr};
+use std::path::PathBuf;
+
+use crate::{
+    completions, dirs,
+    git::RemoteStatus,
+    github::CloneProtocol,
+    shell::{self, Shell},
+};
 
 #[derive(Debug, Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, subcommand_negates_reqs = true)]
 pub struct Args {
     /// Directory where the new git worktrees will be stored
     #[arg(
         short = 'd',
         long = "worktrees-dir",
         value_name = "DIR",
-        env = "SHANTI_WORKTREES_DIR"
+        value_hint = ValueHint::DirPath,
+        env = "SHANTI_WORKTREES_DIR",
+        required = true,
+        // Only used by subcommands, which don't need it.
+        default_value = "",
+        hide_default_value = true
     )]
     // TODO: list worktrees from the repositories directly instead of getting the worktrees_dir from user
     pub worktrees_dir: String,
@@ -17,7 +32,9 @@ pub struct Args {
         short = 'r',
         long = "repos-dir",
         value_name = "DIR",
+        value_hint = ValueHint::DirPath,
         env = "SHANTI_REPOS_DIR",
+        global = true,
         num_args = 1..,
         value_delimiter = ':'
     )]
@@ -31,32 +48,139 @@ pub struct Args {
         default_value_t = false
     )]
     pub run_fetch: bool,
+
+    /// Whether to skip looking up the PR status of each worktree on GitHub. Default: false
+    #[arg(
+        long = "no-pr-status",
+        env = "SHANTI_NO_PR_STATUS",
+        default_value_t = false
+    )]
+    pub no_pr_status: bool,
+
+    /// Protocol used to clone repositories from GitHub
+    #[arg(
+        long = "clone-protocol",
+        value_name = "PROTOCOL",
+        env = "SHANTI_CLONE_PROTOCOL",
+        value_enum,
+        default_value_t = CloneProtocol::Ssh
+    )]
+    pub clone_protocol: CloneProtocol,
+
+    /// Whether to make partial clones (--filter=blob:none), which fetch file contents on demand. Default: false
+    #[arg(
+        long = "partial-clone",
+        env = "SHANTI_PARTIAL_CLONE",
+        default_value_t = false
+    )]
+    pub partial_clone: bool,
+
+    /// Print the selected path followed by a NUL byte instead of a newline
+    #[arg(long = "print0", default_value_t = false)]
+    pub print0: bool,
+
+    /// Write the selected path to FILE instead of stdout. The file is left empty when nothing is selected
+    #[arg(long = "output-file", value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub output_file: Option<PathBuf>,
+
+    /// Where the history, worktree metadata and PR status cache are kept:
+    /// `SHANTI_DATA` or the platform's data directory, resolved by `try_new`.
+    #[arg(skip)]
+    pub data_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print a shell function that wraps shanti and changes to the selected
+    /// worktree, e.g. `eval "$(shanti init bash)"`
+    Init {
+        shell: Shell,
+        /// Also open shanti with Ctrl+KEY from the prompt; Ctrl+G without a KEY
+        #[arg(
+            long,
+            value_name = "KEY",
+            num_args = 0..=1,
+            default_missing_value = "g",
+            value_parser = shell::parse_key
+        )]
+        bind: Option<char>,
+    },
+    /// Print the completion script for a shell, e.g.
+    /// `source <(shanti completions bash)`
+    Completions { shell: clap_complete::Shell },
+    /// Print the man page, e.g. `shanti man > ~/.local/share/man/man1/shanti.1`
+    Man,
+    /// Run a command in each matching worktree of the repos dir, e.g.
+    /// `shanti exec --repo api -- git pull --ff-only`
+    Exec {
+        /// Only the worktrees of this repository
+        #[arg(
+            long,
+            value_name = "REPO",
+            add = ArgValueCompleter::new(completions::repositories)
+        )]
+        repo: Option<String>,
+        /// Only the worktrees matching this filter, as typed above the list, e.g. `login is:dirty`
+        #[arg(
+            long,
+            value_name = "QUERY",
+            add = ArgValueCompleter::new(completions::branches)
+        )]
+        filter: Option<String>,
+        /// Only the worktrees whose remote branch is in this state
+        #[arg(long, value_enum)]
+        status: Option<RemoteStatus>,
+        /// Number of worktrees the command runs in at once
+        #[arg(
+            short = 'j',
+            long,
+            value_name = "N",
+            default_value_t = 1,
+            value_parser = clap::value_parser!(u16).range(1..)
+        )]
+        parallel: u16,
+        /// The command; a single argument is run by sh, so it can use pipes and &&
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
 }
 
 impl Args {
-    pub fn new() -> Self {
+    /// Parses the command line and resolves the directories to absolute paths.
+    pub fn try_new() -> eyre::Result<Self> {
         let mut args = Self::parse();
+        args.data_dir = dirs::get_data_dir().ok();
+        match args.command {
+            None => {}
+            Some(Command::Exec { .. }) if args.repos_dirs.is_empty() => {
+                eyre::bail!("shanti exec needs --repos-dir or SHANTI_REPOS_DIR")
+            }
+            // Only exec looks up the repositories.
+            Some(Command::Exec { .. }) => {}
+            Some(_) => return Ok(args),
+        }
         args.repos_dirs = args
             .repos_dirs
             .iter()
-            .map(|dir| {
-                std::fs::canonicalize(
-                    expand_tilde::expand_tilde(dir).expect("Could not expand the ~ in a repos_dir"),
-                )
-                .expect("Could not resolve repos_dir to an absolute path")
-                .to_str()
-                .expect("Could not convert the expanded repos_dir to a string")
-                .to_string()
-            })
-            .collect();
-        args.worktrees_dir = std::fs::canonicalize(
-            expand_tilde::expand_tilde(&args.worktrees_dir)
-                .expect("Could not expand the ~ in the worktrees_dir"),
-        )
-        .expect("Could not resolve worktrees_dir to an absolute path")
-        .to_str()
-        .expect("Could not convert the expanded worktrees_dir to a string")
-        .to_string();
-        args
+            .map(|dir| absolute_dir(dir, "repos dir"))
+            .collect::<eyre::Result<_>>()?;
+        if args.command.is_none() {
+            args.worktrees_dir = absolute_dir(&args.worktrees_dir, "worktrees dir")?;
+        }
+        Ok(args)
     }
 }
+
+fn absolute_dir(dir: &str, what: &str) -> eyre::Result<String> {
+    let expanded = expand_tilde::expand_tilde(dir)
+        .map_err(|e| eyre!("Could not expand the ~ in the {} {:?}: {}", what, dir, e))?;
+    let absolute = std::fs::canonicalize(&expanded)
+        .wrap_err_with(|| format!("Could not resolve the {} {:?}", what, dir))?;
+    absolute
+        .to_str()
+        .map(String::from)
+        .ok_or_else(|| eyre!("The {} {:?} is not valid UTF-8", what, absolute))
+}
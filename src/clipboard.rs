@@ -0,0 +1,83 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use color_eyre::eyre::{self, WrapErr};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Clipboard tools tried in turn, with the environment variable that tells
+/// they can reach a clipboard.
+const TOOLS: [(&str, &[&str], Option<&str>); 4] = [
+    ("wl-copy", &[], Some("WAYLAND_DISPLAY")),
+    ("xclip", &["-selection", "clipboard"], Some("DISPLAY")),
+    ("xsel", &["--clipboard", "--input"], Some("DISPLAY")),
+    ("pbcopy", &[], None),
+];
+
+/// Puts `text` on the clipboard and returns how. Over SSH and in tmux the
+/// OSC 52 escape reaches the clipboard of the terminal the user sits at; a
+/// local clipboard tool is used otherwise, and OSC 52 when none works.
+pub fn copy(text: &str) -> eyre::Result<&'static str> {
+    let remote =
+        std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some();
+    let tmux = std::env::var_os("TMUX").is_some();
+    if !remote && !tmux {
+        for (program, args, requires) in TOOLS {
+            if requires.is_some_and(|var| std::env::var_os(var).is_none()) {
+                continue;
+            }
+            if copy_with(program, args, text).is_ok() {
+                return Ok(program);
+            }
+        }
+    }
+    let mut stderr = std::io::stderr();
+    stderr
+        .write_all(osc52(text, tmux).as_bytes())
+        .and_then(|_| stderr.flush())
+        .wrap_err("Could not write to the terminal")?;
+    Ok("OSC 52")
+}
+
+fn copy_with(program: &str, args: &[&str], text: &str) -> eyre::Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .wrap_err_with(|| format!("Could not run {}", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        eyre::bail!("{} failed with {}", program, status);
+    }
+    Ok(())
+}
+
+/// The OSC 52 escape setting the clipboard to `text`. In tmux it is wrapped in
+/// a passthrough sequence, which tmux forwards with `allow-passthrough on`.
+fn osc52(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", BASE64_STANDARD.encode(text));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(osc52("/wt/api", false), "\x1b]52;c;L3d0L2FwaQ==\x07");
+        assert_eq!(
+            osc52("/wt/api", true),
+            "\x1bPtmux;\x1b\x1b]52;c;L3d0L2FwaQ==\x07\x1b\\"
+        );
+    }
+}
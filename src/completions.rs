@@ -0,0 +1,118 @@
+use clap::CommandFactory;
+use clap_complete::{env::Shells, CompletionCandidate, Shell};
+use color_eyre::eyre::{self, eyre, WrapErr};
+use std::ffi::OsStr;
+
+use crate::{cli::Args, git};
+
+/// Environment variable the completion scripts set when they call shanti
+/// back for the candidates.
+const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Answers a completion request from one of the scripts of `shanti
+/// completions` and exits; does nothing on a regular run.
+pub fn complete_if_requested() {
+    clap_complete::CompleteEnv::with_factory(Args::command)
+        .var(COMPLETE_VAR)
+        .complete();
+}
+
+/// The completion script of `shanti completions`. It calls this binary by its
+/// absolute path, so the `shanti` function of `shanti init` doesn't get in the
+/// way, and completes values such as repository names as you type.
+pub fn script(shell: Shell) -> eyre::Result<String> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell.to_string())
+        .ok_or_else(|| eyre!("No completions for {}", shell))?;
+    let exe = std::env::current_exe().wrap_err("Could not find the shanti binary")?;
+    let mut script = Vec::new();
+    completer.write_registration(
+        COMPLETE_VAR,
+        "shanti",
+        "shanti",
+        &exe.to_string_lossy(),
+        &mut script,
+    )?;
+    Ok(String::from_utf8_lossy(&script).to_string())
+}
+
+/// Names of the repositories in `$SHANTI_REPOS_DIR` starting with `current`.
+/// The shell only passes the word being completed, so `-r` on the command
+/// line isn't taken into account.
+pub fn repositories(current: &OsStr) -> Vec<CompletionCandidate> {
+    repository_candidates(&repos_dirs(), &current.to_string_lossy())
+}
+
+/// Branches checked out in the worktrees of the repositories in
+/// `$SHANTI_REPOS_DIR` starting with `current`, with their repository as help.
+pub fn branches(current: &OsStr) -> Vec<CompletionCandidate> {
+    branch_candidates(&repos_dirs(), &current.to_string_lossy())
+}
+
+fn repos_dirs() -> Vec<String> {
+    std::env::var("SHANTI_REPOS_DIR")
+        .map(|dirs| dirs.split(':').map(String::from).collect())
+        .unwrap_or_default()
+}
+
+fn repository_candidates(dirs: &[String], current: &str) -> Vec<CompletionCandidate> {
+    let mut names: Vec<String> = dirs
+        .iter()
+        .flat_map(|dir| git::list_repositories(dir, false))
+        .map(|repo| repo.name())
+        .filter(|name| name.starts_with(current))
+        .collect();
+    names.sort();
+    names.dedup();
+    names.into_iter().map(CompletionCandidate::new).collect()
+}
+
+fn branch_candidates(dirs: &[String], current: &str) -> Vec<CompletionCandidate> {
+    let repositories: Vec<_> = dirs
+        .iter()
+        .flat_map(|dir| git::list_repositories(dir, false))
+        .collect();
+    let mut branches: Vec<(String, String)> = repositories
+        .iter()
+        .flat_map(|repo| {
+            let name = repo.name();
+            repo.worktrees()
+                .into_iter()
+                .map(move |worktree| (worktree.branch, name.clone()))
+        })
+        .filter(|(branch, _)| branch.starts_with(current))
+        .collect();
+    branches.sort();
+    branches.dedup_by(|a, b| a.0 == b.0);
+    branches
+        .into_iter()
+        .map(|(branch, repo)| CompletionCandidate::new(branch).help(Some(repo.into())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(candidates: &[CompletionCandidate]) -> Vec<String> {
+        candidates
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_repository_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["api", "app", "web"] {
+            git2::Repository::init(dir.path().join(name)).unwrap();
+        }
+        let dirs = [dir.path().to_str().unwrap().to_string()];
+        assert_eq!(values(&repository_candidates(&dirs, "ap")), ["api", "app"]);
+        assert_eq!(values(&repository_candidates(&dirs, "")).len(), 3);
+        assert!(branch_candidates(&dirs, "").is_empty());
+
+        assert!(script(Shell::Bash).unwrap().contains(COMPLETE_VAR));
+    }
+}
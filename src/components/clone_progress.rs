@@ -0,0 +1,67 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Gauge, Paragraph, Widget},
+    Frame,
+};
+
+use crate::{git::CloneProgress, theme};
+
+pub struct CloneProgressComponent {
+    repo: String,
+    progress: Option<CloneProgress>,
+}
+
+impl CloneProgressComponent {
+    pub fn new(repo: String) -> Self {
+        Self {
+            repo,
+            progress: None,
+        }
+    }
+
+    pub fn update(&mut self, progress: CloneProgress) {
+        self.progress = Some(progress);
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        frame.render_widget(Clear, area);
+
+        let outer_block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.popup_border())
+            .title(Line::from(" Cloning ").style(Style::new().fg(theme.accent_bright).bold()));
+
+        let inner_area = outer_block.inner(area);
+        outer_block.render(area, frame.buffer_mut());
+
+        let [_, label_area, _, gauge_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .horizontal_margin(4)
+        .areas(inner_area);
+
+        Paragraph::new(format!("Cloning {}", self.repo))
+            .style(Style::new().fg(theme.text).bold())
+            .render(label_area, frame.buffer_mut());
+
+        let (label, ratio) = match &self.progress {
+            Some(p) if p.total > 0 => (
+                format!("{} {}/{}", p.stage, p.current, p.total),
+                (p.current as f64 / p.total as f64).min(1.0),
+            ),
+            Some(p) => (p.stage.clone(), 0.0),
+            None => ("Connecting…".to_string(), 0.0),
+        };
+        Gauge::default()
+            .gauge_style(Style::new().fg(theme.accent).bg(theme.faint))
+            .ratio(ratio)
+            .label(label)
+            .render(gauge_area, frame.buffer_mut());
+    }
+}
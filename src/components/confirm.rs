@@ -1,20 +1,26 @@
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
-    style::{
-        palette::tailwind::{GREEN, RED, SLATE},
-        Style, Stylize,
-    },
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, BorderType, Clear, Paragraph, Widget},
     Frame,
 };
 
 use super::{Action, EventState};
+use crate::theme;
+
+/// Most affected items listed before the rest are summed up.
+const MAX_ITEMS: usize = 10;
 
 pub struct ConfirmComponent {
     pub title: String,
     pub label: String,
     pub detail: String,
+    /// Everything the confirmed action applies to, listed under the label.
+    pub items: Vec<String>,
+    /// Where the confirm and cancel hints were last drawn, to make them
+    /// clickable.
+    buttons: [Rect; 2],
 }
 
 impl ConfirmComponent {
@@ -23,55 +29,126 @@ impl ConfirmComponent {
             title,
             label,
             detail,
+            items: vec![],
+            buttons: [Rect::default(); 2],
+        }
+    }
+
+    pub fn with_items(mut self, items: Vec<String>) -> Self {
+        self.items = items;
+        self
+    }
+
+    pub fn height(&self) -> u16 {
+        8 + self.item_lines().len() as u16
+    }
+
+    fn item_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .items
+            .iter()
+            .take(MAX_ITEMS)
+            .map(|item| format!("• {}", item))
+            .collect();
+        if self.items.len() > MAX_ITEMS {
+            lines.push(format!("… and {} more", self.items.len() - MAX_ITEMS));
+        }
+        if !lines.is_empty() {
+            lines.push(String::new());
         }
+        lines
     }
 
     pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
         frame.render_widget(Clear, area);
 
         let title = Line::from(vec![
-            Span::styled(" ⚠ ", Style::new().fg(RED.c400).bold()),
-            Span::styled(self.title.clone(), Style::new().fg(GREEN.c300).bold()),
+            Span::styled(
+                format!(" {} ", theme.glyphs.warning),
+                Style::new().fg(theme.danger).bold(),
+            ),
+            Span::styled(
+                self.title.clone(),
+                Style::new().fg(theme.accent_bright).bold(),
+            ),
             Span::raw(" "),
         ]);
 
+        let hint = keybinding_hint();
+        self.buttons = button_areas(&hint, area);
         let outer_block = Block::bordered()
             .border_type(BorderType::Rounded)
-            .border_style(super::POPUP_BORDER_STYLE)
+            .border_style(theme.popup_border())
             .title(title)
-            .title_bottom(keybinding_hint());
+            .title_bottom(hint);
 
         let inner_area = outer_block.inner(area);
         outer_block.render(area, frame.buffer_mut());
 
-        let [_, label_area, _, detail_area] = Layout::vertical([
+        let item_lines = self.item_lines();
+        let [_, label_area, _, items_area, detail_area] = Layout::vertical([
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(1),
+            Constraint::Length(item_lines.len() as u16),
             Constraint::Length(1),
         ])
         .horizontal_margin(4)
         .areas(inner_area);
 
         Paragraph::new(self.label.as_str())
-            .style(Style::new().fg(SLATE.c200).bold())
+            .style(Style::new().fg(theme.text).bold())
             .render(label_area, frame.buffer_mut());
+        Paragraph::new(item_lines.into_iter().map(Line::from).collect::<Vec<_>>())
+            .style(Style::new().fg(theme.text_muted))
+            .render(items_area, frame.buffer_mut());
         Paragraph::new(format!(" {} ", self.detail))
-            .style(Style::new().fg(RED.c300).bg(RED.c950).bold())
+            .style(
+                Style::new()
+                    .fg(theme.danger_soft)
+                    .bg(theme.danger_bg)
+                    .bold(),
+            )
             .render(detail_area, frame.buffer_mut());
     }
 
     pub fn handle_action(&mut self, _action: Action) -> EventState {
         EventState::NotConsumed
     }
+
+    /// The action of the button at the clicked terminal cell: `Select` to
+    /// confirm, `ClosePopup` to cancel.
+    pub fn button_at(&self, column: u16, row: u16) -> Option<Action> {
+        let position = Position::new(column, row);
+        if self.buttons[0].contains(position) {
+            Some(Action::Select)
+        } else if self.buttons[1].contains(position) {
+            Some(Action::ClosePopup)
+        } else {
+            None
+        }
+    }
+}
+
+/// Areas of the confirm and cancel hints in the bottom border of `area`, where
+/// the right-aligned hint is drawn between the corners.
+fn button_areas(hint: &Line, area: Rect) -> [Rect; 2] {
+    let widths: Vec<u16> = hint.spans.iter().map(|s| s.width() as u16).collect();
+    let start = area.right().saturating_sub(1 + widths.iter().sum::<u16>());
+    let y = area.bottom().saturating_sub(1);
+    let confirm = Rect::new(start, y, widths[0] + widths[1], 1);
+    let cancel = Rect::new(confirm.right(), y, widths[2] + widths[3], 1);
+    [confirm, cancel]
 }
 
 fn keybinding_hint() -> Line<'static> {
+    let theme = theme::current();
     Line::from(vec![
-        Span::styled("[Enter] ", Style::new().fg(RED.c400).bold()),
-        Span::styled("confirm", Style::new().fg(SLATE.c500)),
-        Span::styled("  [Esc] ", Style::new().fg(GREEN.c400).bold()),
-        Span::styled("cancel ", Style::new().fg(SLATE.c500)),
+        Span::styled("[Enter] ", Style::new().fg(theme.danger).bold()),
+        Span::styled("confirm", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("cancel ", Style::new().fg(theme.hint)),
     ])
     .right_aligned()
 }
@@ -0,0 +1,271 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget},
+    Frame,
+};
+
+use super::{Action, EventState};
+use crate::github::NewPullRequest;
+use crate::theme;
+
+const BODY_HEIGHT: u16 = 8;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Title,
+    Base,
+    Body,
+}
+
+pub struct CreatePrComponent {
+    title: String,
+    base: String,
+    body: String,
+    draft: bool,
+    focus: Field,
+    branch: String,
+    pub error: Option<String>,
+}
+
+impl CreatePrComponent {
+    pub fn new() -> Self {
+        Self {
+            title: String::new(),
+            base: String::new(),
+            body: String::new(),
+            draft: false,
+            focus: Field::Title,
+            branch: String::new(),
+            error: None,
+        }
+    }
+
+    /// Prefills the popup the way `gh pr create --fill` does: a single commit
+    /// provides the title and body, several commits are listed in the body
+    /// under a title derived from the branch name.
+    pub fn from_commits(branch: String, base: String, commit_messages: &[String]) -> Self {
+        let (title, body) = match commit_messages {
+            [message] => match message.split_once('\n') {
+                Some((summary, body)) => (summary.trim().to_string(), body.trim().to_string()),
+                None => (message.clone(), String::new()),
+            },
+            _ => {
+                let title = branch
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(&branch)
+                    .replace(['-', '_'], " ");
+                let body = commit_messages
+                    .iter()
+                    .map(|message| format!("- {}", message.lines().next().unwrap_or_default()))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                (title, body)
+            }
+        };
+        Self {
+            title,
+            base,
+            body,
+            branch,
+            ..Self::new()
+        }
+    }
+
+    pub fn new_pull_request(&self, head: String) -> NewPullRequest {
+        NewPullRequest {
+            title: self.title.trim().to_string(),
+            body: self.body.clone(),
+            head,
+            base: self.base.trim().to_string(),
+            draft: self.draft,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        self.title.trim()
+    }
+
+    pub fn is_body_focused(&self) -> bool {
+        self.focus == Field::Body
+    }
+
+    pub fn set_error(&mut self, msg: String) {
+        self.error = Some(msg);
+    }
+
+    /// Height the popup needs, including borders.
+    pub fn height() -> u16 {
+        // borders (2) + blank + 2 * (label + input) + label + body + draft + error
+        2 + 1 + 2 * (1 + 3) + 1 + BODY_HEIGHT + 2 + 1 + 1
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        frame.render_widget(Clear, area);
+
+        let outer_block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.popup_border())
+            .title(
+                Line::from(" Create Pull Request ")
+                    .style(Style::new().fg(theme.accent_bright).bold()),
+            )
+            .title_top(
+                Line::from(format!(" branch: {} ", self.branch))
+                    .style(Style::new().fg(theme.text_dim))
+                    .right_aligned(),
+            )
+            .title_bottom(keybinding_hint());
+
+        let inner_area = outer_block.inner(area);
+        outer_block.render(area, frame.buffer_mut());
+
+        let [_, title_label, title_area, base_label, base_area, body_label, rest] =
+            Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .horizontal_margin(4)
+            .areas(inner_area);
+        let [body_area, draft_area, status_area] = Layout::vertical([
+            Constraint::Length(BODY_HEIGHT + 2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(rest);
+
+        for (label, area) in [
+            ("Title:", title_label),
+            ("Base branch:", base_label),
+            ("Body:", body_label),
+        ] {
+            Paragraph::new(label)
+                .style(Style::new().fg(theme.text_muted))
+                .render(area, frame.buffer_mut());
+        }
+
+        Paragraph::new(self.title.as_str())
+            .block(self.input_block(Field::Title))
+            .render(title_area, frame.buffer_mut());
+        Paragraph::new(self.base.as_str())
+            .block(self.input_block(Field::Base))
+            .render(base_area, frame.buffer_mut());
+
+        // Keep the end of the body, where the cursor is, in view.
+        let body_lines = self.body.split('\n').count() as u16;
+        let scroll = body_lines.saturating_sub(BODY_HEIGHT);
+        Paragraph::new(self.body.as_str())
+            .block(self.input_block(Field::Body))
+            .scroll((scroll, 0))
+            .render(body_area, frame.buffer_mut());
+
+        let checkbox = if self.draft { "[x]" } else { "[ ]" };
+        Paragraph::new(Line::from(vec![
+            Span::styled(checkbox, Style::new().fg(theme.accent).bold()),
+            Span::styled(" Draft", Style::new().fg(theme.text_muted)),
+        ]))
+        .render(draft_area, frame.buffer_mut());
+
+        if let Some(err) = &self.error {
+            Paragraph::new(err.as_str())
+                .style(Style::new().fg(theme.danger))
+                .render(status_area, frame.buffer_mut());
+        }
+
+        // input areas: border(1) + padding(1) = offset 2; y+1 skips top border row
+        let (x, y) = match self.focus {
+            Field::Title => (
+                title_area.x + 2 + self.title.chars().count() as u16,
+                title_area.y + 1,
+            ),
+            Field::Base => (
+                base_area.x + 2 + self.base.chars().count() as u16,
+                base_area.y + 1,
+            ),
+            Field::Body => {
+                let last_line = self.body.rsplit('\n').next().unwrap_or_default();
+                (
+                    body_area.x + 2 + last_line.chars().count() as u16,
+                    body_area.y + 1 + body_lines.min(BODY_HEIGHT) - 1,
+                )
+            }
+        };
+        frame.set_cursor_position((x, y));
+    }
+
+    pub fn handle_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::InsertChar(c) => {
+                self.focused_value().push(c);
+                self.error = None;
+                EventState::Consumed
+            }
+            Action::DeleteChar => {
+                self.focused_value().pop();
+                self.error = None;
+                EventState::Consumed
+            }
+            Action::Select if self.is_body_focused() => {
+                self.body.push('\n');
+                EventState::Consumed
+            }
+            Action::FocusNext => {
+                self.focus = match self.focus {
+                    Field::Title => Field::Base,
+                    Field::Base => Field::Body,
+                    Field::Body => Field::Title,
+                };
+                EventState::Consumed
+            }
+            Action::ToggleDraft => {
+                self.draft = !self.draft;
+                EventState::Consumed
+            }
+            _ => EventState::NotConsumed,
+        }
+    }
+
+    fn focused_value(&mut self) -> &mut String {
+        match self.focus {
+            Field::Title => &mut self.title,
+            Field::Base => &mut self.base,
+            Field::Body => &mut self.body,
+        }
+    }
+
+    fn input_block(&self, field: Field) -> Block<'static> {
+        let theme = theme::current();
+        let border_style = if self.focus == field {
+            theme.active_border()
+        } else {
+            theme.border()
+        };
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(border_style)
+            .padding(Padding::horizontal(1))
+    }
+}
+
+fn keybinding_hint() -> Line<'static> {
+    let theme = theme::current();
+    Line::from(vec![
+        Span::styled("[Ctrl+S] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("create", Style::new().fg(theme.hint)),
+        Span::styled("  [Tab] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("next field", Style::new().fg(theme.hint)),
+        Span::styled("  [Ctrl+D] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("draft", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.danger).bold()),
+        Span::styled("cancel ", Style::new().fg(theme.hint)),
+    ])
+    .right_aligned()
+}
@@ -1,15 +1,15 @@
 use ratatui::{
     layout::{Constraint, Layout, Rect},
-    style::{
-        palette::tailwind::{AMBER, GREEN, RED, SLATE},
-        Color, Style, Stylize,
-    },
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget},
     Frame,
 };
 
 use super::{Action, EventState};
+use crate::config::{self, BranchConfig, TemplateValues};
+use crate::git::{Transfer, UntrackedFile};
+use crate::theme;
 
 pub struct CreateWorktreeComponent {
     character_index: usize,
@@ -17,6 +17,16 @@ pub struct CreateWorktreeComponent {
     repo_name: String,
     pub base_branch_hint: Option<String>,
     pub warning: Option<String>,
+    /// Branch name templates to pick from; the input is used as the branch
+    /// name as-is when empty.
+    templates: Vec<String>,
+    template_index: usize,
+    types: Vec<String>,
+    type_index: usize,
+    user: String,
+    date: String,
+    /// Files of the main checkout brought into the new worktree.
+    untracked: Vec<UntrackedFile>,
 }
 
 impl CreateWorktreeComponent {
@@ -27,9 +37,76 @@ impl CreateWorktreeComponent {
             repo_name,
             base_branch_hint: None,
             warning: None,
+            templates: Vec::new(),
+            template_index: 0,
+            types: Vec::new(),
+            type_index: 0,
+            user: String::new(),
+            date: String::new(),
+            untracked: Vec::new(),
         }
     }
 
+    /// Offers `templates` to build the branch name from the input.
+    pub fn with_templates(mut self, templates: Vec<String>, branch_config: &BranchConfig) -> Self {
+        self.templates = templates;
+        self.types = branch_config.types.clone();
+        self.user = branch_config.user();
+        self.date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        self
+    }
+
+    /// Previews `files`, which are brought into the worktree once created.
+    pub fn with_untracked(mut self, files: Vec<UntrackedFile>) -> Self {
+        self.untracked = files;
+        self
+    }
+
+    pub fn untracked_files(&self) -> &[UntrackedFile] {
+        &self.untracked
+    }
+
+    /// The untracked files to copy and to link, as lists of paths.
+    fn untracked_preview(&self) -> Vec<(&'static str, String)> {
+        [
+            (Transfer::Copy, "Copies: "),
+            (Transfer::Symlink, "Links:  "),
+        ]
+        .into_iter()
+        .filter_map(|(transfer, label)| {
+            let paths: Vec<_> = self
+                .untracked
+                .iter()
+                .filter(|file| file.transfer == transfer)
+                .map(|file| file.path.to_string_lossy())
+                .collect();
+            (!paths.is_empty()).then(|| (label, paths.join(", ")))
+        })
+        .collect()
+    }
+
+    /// The branch to create: the input expanded with the selected template.
+    pub fn branch_name(&self) -> String {
+        match self.templates.get(self.template_index) {
+            Some(template) => config::expand_branch_template(
+                template,
+                &TemplateValues {
+                    name: &self.new_worktree_name,
+                    kind: self.types.get(self.type_index).map_or("", |t| t.as_str()),
+                    user: &self.user,
+                    date: &self.date,
+                },
+            ),
+            None => self.new_worktree_name.clone(),
+        }
+    }
+
+    /// Height of the popup, including its borders.
+    pub fn height(&self) -> u16 {
+        let template_rows = if self.templates.is_empty() { 0 } else { 2 };
+        9 + template_rows + self.untracked_preview().len() as u16
+    }
+
     pub fn new_with_branch(
         repo_name: String,
         branch_name: String,
@@ -42,44 +119,91 @@ impl CreateWorktreeComponent {
             repo_name,
             base_branch_hint: None,
             warning,
+            templates: Vec::new(),
+            template_index: 0,
+            types: Vec::new(),
+            type_index: 0,
+            user: String::new(),
+            date: String::new(),
+            untracked: Vec::new(),
         }
     }
 
     pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
         frame.render_widget(Clear, area);
 
+        let branch_name = self.branch_name();
         let input_border_style =
-            if self.new_worktree_name.is_empty() || is_valid_branch_name(&self.new_worktree_name) {
-                super::ACTIVE_BORDER_STYLE
+            if self.new_worktree_name.is_empty() || is_valid_branch_name(&branch_name) {
+                theme.active_border()
             } else {
-                Style::new().fg(Color::Red)
+                Style::new().fg(theme.danger)
             };
 
         let outer_block = Block::bordered()
             .border_type(BorderType::Rounded)
-            .border_style(super::POPUP_BORDER_STYLE)
-            .title(Line::from(" New Worktree ").style(Style::new().fg(GREEN.c300).bold()))
+            .border_style(theme.popup_border())
+            .title(Line::from(" New Worktree ").style(Style::new().fg(theme.accent_bright).bold()))
             .title_top(
                 Line::from(format!(" repo: {} ", self.repo_name))
-                    .style(Style::new().fg(SLATE.c400))
+                    .style(Style::new().fg(theme.text_dim))
                     .right_aligned(),
             )
-            .title_bottom(keybinding_hint());
+            .title_bottom(keybinding_hint(!self.templates.is_empty()));
 
         let inner_area = outer_block.inner(area);
         outer_block.render(area, frame.buffer_mut());
 
-        let [_, label_area, input_area, hint_area] = Layout::vertical([
-            Constraint::Length(1),
-            Constraint::Length(1),
-            Constraint::Length(3),
-            Constraint::Length(1),
-        ])
-        .horizontal_margin(4)
-        .areas(inner_area);
-
-        Paragraph::new("Branch name:")
-            .style(Style::new().fg(SLATE.c300))
+        let template_rows = if self.templates.is_empty() { 0 } else { 2 };
+        let preview = self.untracked_preview();
+        let [_, template_area, label_area, input_area, hint_area, preview_area] =
+            Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Length(template_rows),
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Length(preview.len() as u16),
+            ])
+            .horizontal_margin(4)
+            .areas(inner_area);
+
+        if let Some(template) = self.templates.get(self.template_index) {
+            let mut spans = vec![
+                Span::styled("Template: ", Style::new().fg(theme.text_muted)),
+                Span::styled(template.clone(), Style::new().fg(theme.accent_bright)),
+                Span::styled(
+                    format!("  ({}/{})", self.template_index + 1, self.templates.len()),
+                    Style::new().fg(theme.hint),
+                ),
+            ];
+            if template.contains("{type}") {
+                if let Some(kind) = self.types.get(self.type_index) {
+                    spans.push(Span::styled("  type: ", Style::new().fg(theme.text_muted)));
+                    spans.push(Span::styled(
+                        kind.clone(),
+                        Style::new().fg(theme.warning_soft),
+                    ));
+                }
+            }
+            Paragraph::new(vec![
+                Line::from(spans),
+                Line::from(vec![
+                    Span::styled("Branch:   ", Style::new().fg(theme.text_muted)),
+                    Span::styled(branch_name, Style::new().fg(theme.text).bold()),
+                ]),
+            ])
+            .render(template_area, frame.buffer_mut());
+        }
+
+        let label = if self.templates.is_empty() {
+            "Branch name:"
+        } else {
+            "Name:"
+        };
+        Paragraph::new(label)
+            .style(Style::new().fg(theme.text_muted))
             .render(label_area, frame.buffer_mut());
 
         Paragraph::new(self.new_worktree_name.as_str())
@@ -93,14 +217,25 @@ impl CreateWorktreeComponent {
 
         if let Some(warning) = &self.warning {
             Paragraph::new(warning.as_str())
-                .style(Style::new().fg(AMBER.c300))
+                .style(Style::new().fg(theme.warning_soft))
                 .render(hint_area, frame.buffer_mut());
         } else if let Some(hint) = &self.base_branch_hint {
             Paragraph::new(hint.as_str())
-                .style(Style::new().fg(SLATE.c400))
+                .style(Style::new().fg(theme.text_dim))
                 .render(hint_area, frame.buffer_mut());
         }
 
+        let preview: Vec<Line> = preview
+            .into_iter()
+            .map(|(label, paths)| {
+                Line::from(vec![
+                    Span::styled(label, Style::new().fg(theme.text_muted)),
+                    Span::styled(paths, Style::new().fg(theme.text_dim)),
+                ])
+            })
+            .collect();
+        Paragraph::new(preview).render(preview_area, frame.buffer_mut());
+
         // input_area: border(1) + padding(1) = offset 2; y+1 skips top border row
         frame.set_cursor_position((
             input_area.x + 2 + self.character_index as u16,
@@ -118,6 +253,19 @@ impl CreateWorktreeComponent {
                 self.delete_char();
                 EventState::Consumed
             }
+            Action::MoveDown if !self.templates.is_empty() => {
+                self.template_index = (self.template_index + 1) % self.templates.len();
+                EventState::Consumed
+            }
+            Action::MoveUp if !self.templates.is_empty() => {
+                self.template_index =
+                    (self.template_index + self.templates.len() - 1) % self.templates.len();
+                EventState::Consumed
+            }
+            Action::FocusNext if !self.types.is_empty() => {
+                self.type_index = (self.type_index + 1) % self.types.len();
+                EventState::Consumed
+            }
             _ => EventState::NotConsumed,
         }
     }
@@ -173,21 +321,31 @@ impl CreateWorktreeComponent {
     }
 }
 
-fn keybinding_hint() -> Line<'static> {
-    Line::from(vec![
-        Span::styled("[Enter] ", Style::new().fg(GREEN.c400).bold()),
-        Span::styled("confirm", Style::new().fg(SLATE.c500)),
-        Span::styled("  [Esc] ", Style::new().fg(RED.c400).bold()),
-        Span::styled("cancel ", Style::new().fg(SLATE.c500)),
-    ])
-    .right_aligned()
+fn keybinding_hint(has_templates: bool) -> Line<'static> {
+    let theme = theme::current();
+    let mut spans = Vec::new();
+    if has_templates {
+        spans.extend([
+            Span::styled("[↑↓] ", Style::new().fg(theme.accent).bold()),
+            Span::styled("template", Style::new().fg(theme.hint)),
+            Span::styled("  [Tab] ", Style::new().fg(theme.accent).bold()),
+            Span::styled("type  ", Style::new().fg(theme.hint)),
+        ]);
+    }
+    spans.extend([
+        Span::styled("[Enter] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("confirm", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.danger).bold()),
+        Span::styled("cancel ", Style::new().fg(theme.hint)),
+    ]);
+    Line::from(spans).right_aligned()
 }
 
 fn is_valid_branch_char(c: char) -> bool {
     c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/')
 }
 
-fn is_valid_branch_name(name: &str) -> bool {
+pub fn is_valid_branch_name(name: &str) -> bool {
     if name.is_empty() {
         return false;
     }
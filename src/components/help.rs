@@ -1,15 +1,13 @@
 use ratatui::{
     layout::{Alignment, Margin, Rect},
-    style::{
-        palette::tailwind::{BLUE, GREEN},
-        Style, Stylize,
-    },
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{Block, BorderType, Clear, Paragraph},
     Frame,
 };
 
 use super::{Action, EventState};
+use crate::theme;
 
 pub enum HelpEntry {
     Binding(&'static str, &'static str),
@@ -19,11 +17,22 @@ pub enum HelpEntry {
 
 pub struct HelpComponent {
     pub entries: Vec<HelpEntry>,
+    scroll: u16,
 }
 
 impl HelpComponent {
     pub fn new(entries: Vec<HelpEntry>) -> Self {
-        Self { entries }
+        Self { entries, scroll: 0 }
+    }
+
+    /// Scrolls by one line, for help that does not fit the terminal.
+    pub fn scroll(&mut self, down: bool) {
+        let last = self.dimensions().1.saturating_sub(4);
+        self.scroll = if down {
+            (self.scroll + 1).min(last)
+        } else {
+            self.scroll.saturating_sub(1)
+        };
     }
 
     /// Returns the (width, height) the popup needs, including borders and padding.
@@ -51,12 +60,13 @@ impl HelpComponent {
     }
 
     pub fn draw(&mut self, f: &mut Frame, area: Rect) {
+        let theme = theme::current();
         f.render_widget(Clear, area);
 
         let block = Block::bordered()
             .border_type(BorderType::Rounded)
-            .border_style(super::POPUP_BORDER_STYLE)
-            .title(Line::from(" Help ").style(Style::new().fg(GREEN.c300).bold()))
+            .border_style(theme.popup_border())
+            .title(Line::from(" Help ").style(Style::new().fg(theme.accent_bright).bold()))
             .title_alignment(Alignment::Center);
         f.render_widget(block, area);
 
@@ -69,13 +79,13 @@ impl HelpComponent {
             .iter()
             .flat_map(|e| match e {
                 HelpEntry::Binding(key, desc) => vec![Line::from(vec![
-                    Span::styled(format!("{:<12}", key), Style::new().fg(BLUE.c400).bold()),
+                    Span::styled(format!("{:<12}", key), Style::new().fg(theme.info).bold()),
                     Span::raw(*desc),
                 ])],
                 HelpEntry::Section(title) => vec![
                     Line::from(Span::styled(
                         *title,
-                        Style::new().fg(GREEN.c400).bold().underlined(),
+                        Style::new().fg(theme.accent).bold().underlined(),
                     )),
                     Line::raw(""),
                 ],
@@ -83,7 +93,7 @@ impl HelpComponent {
             })
             .collect();
 
-        f.render_widget(Paragraph::new(rows), inner);
+        f.render_widget(Paragraph::new(rows).scroll((self.scroll, 0)), inner);
     }
 
     pub fn handle_action(&mut self, _action: Action) -> EventState {
@@ -1,4 +1,7 @@
-use ratatui::widgets::ListState;
+use ratatui::{
+    layout::{Position, Rect},
+    widgets::ListState,
+};
 
 pub enum Focus {
     Filter,
@@ -14,7 +17,7 @@ pub enum ItemOrder {
 
 pub trait ListComponent<T> {
     fn select(&mut self, item_order: ItemOrder) {
-        let len = self.filtered_items().len();
+        let len = self.row_count();
         if len == 0 {
             return;
         }
@@ -35,6 +38,43 @@ pub trait ListComponent<T> {
         self.update_selected_index(new_index);
     }
 
+    /// Selects the row drawn at (`column`, `row`) of a list rendered in `area`.
+    /// Returns whether a row was hit.
+    fn select_at(&mut self, area: Rect, column: u16, row: u16) -> bool {
+        if !area.contains(Position::new(column, row)) {
+            return false;
+        }
+        let index = self.get_state().offset() + (row - area.y) as usize;
+        if index >= self.row_count() {
+            return false;
+        }
+        self.get_state().select(Some(index));
+        self.update_selected_index(index);
+        true
+    }
+
+    /// Moves the selection one row, like the mouse wheel: unlike `select`, it
+    /// stops at either end instead of wrapping around.
+    fn scroll_selection(&mut self, down: bool) {
+        let len = self.row_count();
+        let selected = self.get_state().selected().unwrap_or(0);
+        let index = if down {
+            (selected + 1).min(len.saturating_sub(1))
+        } else {
+            selected.saturating_sub(1)
+        };
+        if len > 0 {
+            self.get_state().select(Some(index));
+            self.update_selected_index(index);
+        }
+    }
+
+    /// Number of selectable rows, which may include rows that are not items,
+    /// e.g. group headers.
+    fn row_count(&mut self) -> usize {
+        self.filtered_items().len()
+    }
+
     fn filtered_items(&mut self) -> Vec<&T>;
     fn get_state(&mut self) -> &mut ListState;
     fn update_selected_index(&mut self, index: usize);
@@ -0,0 +1,100 @@
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+use crate::theme;
+
+/// Lines kept before the oldest are dropped.
+const MAX_LINES: usize = 1000;
+
+/// Output of hooks, shown in a panel under the worktrees.
+pub struct LogComponent {
+    lines: Vec<Line<'static>>,
+    pub visible: bool,
+}
+
+impl LogComponent {
+    /// Height of the panel, including its borders.
+    pub const HEIGHT: u16 = 12;
+
+    pub fn new() -> Self {
+        Self {
+            lines: vec![],
+            visible: false,
+        }
+    }
+
+    pub fn push_info(&mut self, message: String) {
+        let theme = theme::current();
+        self.push(Line::from(Span::styled(
+            message,
+            Style::new().fg(theme.accent_bright).bold(),
+        )));
+    }
+
+    pub fn push_command(&mut self, command: &str) {
+        let theme = theme::current();
+        self.push(Line::from(vec![
+            Span::styled("$ ", Style::new().fg(theme.hint)),
+            Span::styled(command.to_string(), Style::new().fg(theme.info).bold()),
+        ]));
+    }
+
+    pub fn push_output(&mut self, line: String) {
+        let theme = theme::current();
+        self.push(Line::from(Span::styled(
+            line,
+            Style::new().fg(theme.text_muted),
+        )));
+    }
+
+    pub fn push_error(&mut self, message: String) {
+        let theme = theme::current();
+        self.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", theme.glyphs.failure),
+                Style::new().fg(theme.danger),
+            ),
+            Span::styled(message, Style::new().fg(theme.danger_soft)),
+        ]));
+    }
+
+    fn push(&mut self, line: Line<'static>) {
+        self.lines.push(line);
+        if self.lines.len() > MAX_LINES {
+            self.lines.drain(..self.lines.len() - MAX_LINES);
+        }
+    }
+
+    /// Draws the latest lines, so running output follows along.
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        frame.render_widget(Clear, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border())
+            .title(Line::from(vec![
+                Span::raw(" "),
+                Span::styled("Log", Style::new().fg(theme.accent).bold()),
+                Span::raw(" "),
+            ]))
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" L ", Style::new().fg(theme.info).bold()),
+                    Span::styled("hide ", Style::new().fg(theme.hint)),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let skip = self.lines.len().saturating_sub(inner.height as usize);
+        let lines: Vec<Line> = self.lines[skip..].to_vec();
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
@@ -0,0 +1,216 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget},
+    Frame,
+};
+
+use super::{Action, EventState};
+use crate::metadata::WorktreeMetadata;
+use crate::theme;
+
+const NOTES_HEIGHT: u16 = 6;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Tags,
+    Tickets,
+    Notes,
+}
+
+/// Popup to edit the tags, ticket links and notes of a worktree.
+pub struct MetadataComponent {
+    tags: String,
+    tickets: String,
+    notes: String,
+    /// Not editable here, kept so saving does not unpin the worktree.
+    pinned: bool,
+    focus: Field,
+    worktree: String,
+}
+
+impl MetadataComponent {
+    pub fn new(worktree: String, metadata: &WorktreeMetadata) -> Self {
+        Self {
+            tags: metadata.tags.join(", "),
+            tickets: metadata.tickets.join(" "),
+            notes: metadata.notes.clone(),
+            pinned: metadata.pinned,
+            focus: Field::Tags,
+            worktree,
+        }
+    }
+
+    /// Tags are separated by commas, tickets by commas or whitespace.
+    pub fn metadata(&self) -> WorktreeMetadata {
+        WorktreeMetadata {
+            notes: self.notes.trim().to_string(),
+            tickets: self
+                .tickets
+                .split([',', ' ', '\n'])
+                .map(str::trim)
+                .filter(|ticket| !ticket.is_empty())
+                .map(str::to_string)
+                .collect(),
+            tags: self
+                .tags
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect(),
+            pinned: self.pinned,
+        }
+    }
+
+    pub fn is_notes_focused(&self) -> bool {
+        self.focus == Field::Notes
+    }
+
+    /// Height the popup needs, including borders.
+    pub fn height() -> u16 {
+        // borders (2) + blank + 2 * (label + input) + label + notes
+        2 + 1 + 2 * (1 + 3) + 1 + NOTES_HEIGHT + 2
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        frame.render_widget(Clear, area);
+
+        let outer_block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.popup_border())
+            .title(
+                Line::from(" Worktree Metadata ")
+                    .style(Style::new().fg(theme.accent_bright).bold()),
+            )
+            .title_top(
+                Line::from(format!(" {} ", self.worktree))
+                    .style(Style::new().fg(theme.text_dim))
+                    .right_aligned(),
+            )
+            .title_bottom(keybinding_hint());
+
+        let inner_area = outer_block.inner(area);
+        outer_block.render(area, frame.buffer_mut());
+
+        let [_, tags_label, tags_area, tickets_label, tickets_area, notes_label, notes_area] =
+            Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Length(NOTES_HEIGHT + 2),
+            ])
+            .horizontal_margin(4)
+            .areas(inner_area);
+
+        for (label, area) in [
+            ("Tags (comma-separated):", tags_label),
+            ("Ticket links:", tickets_label),
+            ("Notes:", notes_label),
+        ] {
+            Paragraph::new(label)
+                .style(Style::new().fg(theme.text_muted))
+                .render(area, frame.buffer_mut());
+        }
+
+        Paragraph::new(self.tags.as_str())
+            .block(self.input_block(Field::Tags))
+            .render(tags_area, frame.buffer_mut());
+        Paragraph::new(self.tickets.as_str())
+            .block(self.input_block(Field::Tickets))
+            .render(tickets_area, frame.buffer_mut());
+
+        // Keep the end of the notes, where the cursor is, in view.
+        let notes_lines = self.notes.split('\n').count() as u16;
+        let scroll = notes_lines.saturating_sub(NOTES_HEIGHT);
+        Paragraph::new(self.notes.as_str())
+            .block(self.input_block(Field::Notes))
+            .scroll((scroll, 0))
+            .render(notes_area, frame.buffer_mut());
+
+        // input areas: border(1) + padding(1) = offset 2; y+1 skips top border row
+        let (x, y) = match self.focus {
+            Field::Tags => (
+                tags_area.x + 2 + self.tags.chars().count() as u16,
+                tags_area.y + 1,
+            ),
+            Field::Tickets => (
+                tickets_area.x + 2 + self.tickets.chars().count() as u16,
+                tickets_area.y + 1,
+            ),
+            Field::Notes => {
+                let last_line = self.notes.rsplit('\n').next().unwrap_or_default();
+                (
+                    notes_area.x + 2 + last_line.chars().count() as u16,
+                    notes_area.y + 1 + notes_lines.min(NOTES_HEIGHT) - 1,
+                )
+            }
+        };
+        frame.set_cursor_position((x, y));
+    }
+
+    pub fn handle_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::InsertChar(c) => {
+                self.focused_value().push(c);
+                EventState::Consumed
+            }
+            Action::DeleteChar => {
+                self.focused_value().pop();
+                EventState::Consumed
+            }
+            Action::Select if self.is_notes_focused() => {
+                self.notes.push('\n');
+                EventState::Consumed
+            }
+            Action::FocusNext => {
+                self.focus = match self.focus {
+                    Field::Tags => Field::Tickets,
+                    Field::Tickets => Field::Notes,
+                    Field::Notes => Field::Tags,
+                };
+                EventState::Consumed
+            }
+            _ => EventState::NotConsumed,
+        }
+    }
+
+    fn focused_value(&mut self) -> &mut String {
+        match self.focus {
+            Field::Tags => &mut self.tags,
+            Field::Tickets => &mut self.tickets,
+            Field::Notes => &mut self.notes,
+        }
+    }
+
+    fn input_block(&self, field: Field) -> Block<'static> {
+        let theme = theme::current();
+        let border_style = if self.focus == field {
+            theme.active_border()
+        } else {
+            theme.border()
+        };
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(border_style)
+            .padding(Padding::horizontal(1))
+    }
+}
+
+fn keybinding_hint() -> Line<'static> {
+    let theme = theme::current();
+    Line::from(vec![
+        Span::styled("[Ctrl+S] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("save", Style::new().fg(theme.hint)),
+        Span::styled("  [Tab] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("next field", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.danger).bold()),
+        Span::styled("cancel ", Style::new().fg(theme.hint)),
+    ])
+    .right_aligned()
+}
@@ -1,34 +1,37 @@
+mod clone_progress;
 mod confirm;
+mod create_pr;
 mod create_worktree;
 mod filter;
 mod help;
 mod list;
+mod log;
+mod metadata;
 mod pr_worktree;
+mod query;
 mod repositories;
+mod run_command;
 mod select_directory;
+mod summary;
+mod ticket;
 mod worktrees;
 
+pub use clone_progress::CloneProgressComponent;
 pub use confirm::ConfirmComponent;
-pub use create_worktree::CreateWorktreeComponent;
+pub use create_pr::CreatePrComponent;
+pub use create_worktree::{is_valid_branch_name, CreateWorktreeComponent};
 pub use help::{HelpComponent, HelpEntry};
+pub use log::LogComponent;
+pub use metadata::MetadataComponent;
 pub use pr_worktree::PrWorktreeComponent;
-use ratatui::style::{
-    palette::tailwind::{BLUE, GREEN, SLATE},
-    Modifier, Style,
-};
+pub use query::{Query, WordMatcher, WorktreeFacts};
 pub use repositories::RepositoriesComponent;
+pub use run_command::RunCommandComponent;
 pub use select_directory::SelectDirectoryComponent;
+pub use summary::{Outcome, SummaryComponent};
+pub use ticket::TicketComponent;
 pub use worktrees::WorktreesComponent;
 
-/// Selected item: blue bg matching lazygit's selectedLineBgColor.
-const SELECTED_STYLE: Style = Style::new().bg(BLUE.c800).add_modifier(Modifier::BOLD);
-/// Muted border for the main panel — lazygit inactiveBorderColor is terminal default.
-const BORDER_STYLE: Style = Style::new().fg(SLATE.c500);
-/// Green bold border for popups — lazygit activeBorderColor is [green, bold].
-const POPUP_BORDER_STYLE: Style = Style::new().fg(GREEN.c400).add_modifier(Modifier::BOLD);
-/// Brighter green bold border for focused inputs — one step lighter than popup border.
-const ACTIVE_BORDER_STYLE: Style = Style::new().fg(GREEN.c300).add_modifier(Modifier::BOLD);
-
 #[derive(PartialEq, Debug)]
 pub enum EventState {
     Consumed,
@@ -49,6 +52,27 @@ pub enum Action {
     OpenRepositories,
     OpenPrWorktree,
     OpenPrWorktreeAutoClone,
+    OpenCreatePr,
+    OpenTicketWorktree,
+    OpenMetadata,
+    CycleSort,
+    TogglePin,
+    ToggleGrouped,
+    Collapse,
+    Expand,
+    ToggleMark,
+    ToggleVisual,
+    ToggleMarkAll,
+    Prune,
+    Fetch,
+    Pull,
+    OpenInEditor,
+    OpenTerminal,
+    RunCommand,
+    CopyPath,
+    ToggleLog,
+    ToggleDraft,
+    Submit,
     ClosePopup,
     EnterInsertMode,
     ExitInsertMode,
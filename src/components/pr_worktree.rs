@@ -1,15 +1,13 @@
 use ratatui::{
     layout::{Constraint, Layout, Rect},
-    style::{
-        palette::tailwind::{GREEN, RED, SLATE},
-        Style, Stylize,
-    },
+    style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget},
+    widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget, Wrap},
     Frame,
 };
 
 use super::{Action, EventState};
+use crate::theme;
 
 pub struct PrWorktreeComponent {
     character_index: usize,
@@ -44,12 +42,15 @@ impl PrWorktreeComponent {
     }
 
     pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
         frame.render_widget(Clear, area);
 
         let outer_block = Block::bordered()
             .border_type(BorderType::Rounded)
-            .border_style(super::POPUP_BORDER_STYLE)
-            .title(Line::from(" Worktree from PR ").style(Style::new().fg(GREEN.c300).bold()))
+            .border_style(theme.popup_border())
+            .title(
+                Line::from(" Worktree from PR ").style(Style::new().fg(theme.accent_bright).bold()),
+            )
             .title_bottom(keybinding_hint());
 
         let inner_area = outer_block.inner(area);
@@ -59,27 +60,29 @@ impl PrWorktreeComponent {
             Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Length(3),
-            Constraint::Length(1),
+            Constraint::Length(2),
         ])
         .horizontal_margin(4)
         .areas(inner_area);
 
         Paragraph::new("GitHub PR URL:")
-            .style(Style::new().fg(SLATE.c300))
+            .style(Style::new().fg(theme.text_muted))
             .render(label_area, frame.buffer_mut());
 
         Paragraph::new(self.input.as_str())
             .block(
                 Block::bordered()
                     .border_type(BorderType::Rounded)
-                    .border_style(super::ACTIVE_BORDER_STYLE)
+                    .border_style(theme.active_border())
                     .padding(Padding::horizontal(1)),
             )
             .render(input_area, frame.buffer_mut());
 
         if let Some(err) = &self.error {
+            // Errors end with what to do about them, so let them wrap rather than cut off.
             Paragraph::new(err.as_str())
-                .style(Style::new().fg(RED.c400))
+                .style(Style::new().fg(theme.danger))
+                .wrap(Wrap { trim: true })
                 .render(status_area, frame.buffer_mut());
         }
 
@@ -142,11 +145,12 @@ impl PrWorktreeComponent {
 }
 
 fn keybinding_hint() -> Line<'static> {
+    let theme = theme::current();
     Line::from(vec![
-        Span::styled("[Enter] ", Style::new().fg(GREEN.c400).bold()),
-        Span::styled("open", Style::new().fg(SLATE.c500)),
-        Span::styled("  [Esc] ", Style::new().fg(RED.c400).bold()),
-        Span::styled("cancel ", Style::new().fg(SLATE.c500)),
+        Span::styled("[Enter] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("open", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.danger).bold()),
+        Span::styled("cancel ", Style::new().fg(theme.hint)),
     ])
     .right_aligned()
 }
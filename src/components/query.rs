@@ -0,0 +1,264 @@
+use nucleo_matcher::{
+    pattern::{CaseMatching, Normalization, Pattern},
+    Config, Matcher, Utf32Str,
+};
+
+use crate::git::RemoteStatus;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// A worktree filter: words fuzzy-matched against the `repo/branch` display
+/// string, and qualifiers such as `repo:api`, `status:gone`, `is:dirty`,
+/// `tag:urgent` or `age:>30d`. A qualifier prefixed with `!` is negated.
+#[derive(Debug, Default, PartialEq)]
+pub struct Query {
+    pub words: Vec<String>,
+    pub conditions: Vec<Condition>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Condition {
+    pub negated: bool,
+    pub qualifier: Qualifier,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Qualifier {
+    /// Repository name contains the value, ignoring case.
+    Repo(String),
+    Status(RemoteStatus),
+    Dirty,
+    Clean,
+    Pinned,
+    Tag(String),
+    /// Age of the last commit, in seconds: older than when `older` is set,
+    /// otherwise newer than.
+    Age {
+        older: bool,
+        seconds: u64,
+    },
+    /// A qualifier whose value cannot be parsed, e.g. `status:foo`. Matches
+    /// nothing, so typos do not go unnoticed.
+    Invalid,
+}
+
+/// What the conditions are checked against for a worktree.
+pub struct WorktreeFacts<'a> {
+    pub repo: &'a str,
+    pub remote_status: RemoteStatus,
+    pub is_dirty: bool,
+    pub pinned: bool,
+    pub tags: &'a [String],
+    pub last_commit_time: Option<i64>,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Self {
+        let mut query = Query::default();
+        for word in input.split_whitespace() {
+            match parse_condition(word) {
+                Some(condition) => query.conditions.push(condition),
+                None => query.words.push(word.to_string()),
+            }
+        }
+        query
+    }
+
+    pub fn matches(&self, facts: &WorktreeFacts, now: u64) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(facts, now))
+    }
+}
+
+impl Condition {
+    fn matches(&self, facts: &WorktreeFacts, now: u64) -> bool {
+        let matches = match &self.qualifier {
+            Qualifier::Repo(repo) => facts.repo.to_lowercase().contains(repo),
+            Qualifier::Status(status) => facts.remote_status == *status,
+            Qualifier::Dirty => facts.is_dirty,
+            Qualifier::Clean => !facts.is_dirty,
+            Qualifier::Pinned => facts.pinned,
+            Qualifier::Tag(tag) => facts.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+            Qualifier::Age { older, seconds } => match facts.last_commit_time {
+                Some(time) => {
+                    let age = now.saturating_sub(time.max(0) as u64);
+                    if *older {
+                        age > *seconds
+                    } else {
+                        age < *seconds
+                    }
+                }
+                None => false,
+            },
+            Qualifier::Invalid => false,
+        };
+        matches != self.negated
+    }
+}
+
+/// Fuzzy-matches the words of a query, all of which must match.
+pub struct WordMatcher {
+    matcher: Matcher,
+    /// Each word with its minimum score.
+    patterns: Vec<(Pattern, u32)>,
+    buf: Vec<char>,
+}
+
+impl WordMatcher {
+    pub fn new(words: &[String]) -> Self {
+        // Short words (1-2 chars) have low scores due to gap penalties on
+        // longer haystacks, so we accept any match for them.
+        let patterns = words
+            .iter()
+            .map(|w| {
+                let min = if w.len() >= 3 { 70 } else { 1 };
+                (
+                    Pattern::parse(w, CaseMatching::Ignore, Normalization::Smart),
+                    min,
+                )
+            })
+            .collect();
+        Self {
+            matcher: Matcher::new(Config::DEFAULT),
+            patterns,
+            buf: Vec::new(),
+        }
+    }
+
+    /// The total score of `haystack`, or `None` when a word doesn't match it.
+    pub fn score(&mut self, haystack: &str) -> Option<u32> {
+        let mut total = 0u32;
+        for (pattern, min_score) in &self.patterns {
+            match pattern.score(Utf32Str::new(haystack, &mut self.buf), &mut self.matcher) {
+                Some(s) if s >= *min_score => total += s,
+                _ => return None,
+            }
+        }
+        Some(total)
+    }
+}
+
+/// Parses `key:value` or `!key:value`; `None` for words that are not
+/// qualifiers, which are then fuzzy-matched.
+fn parse_condition(word: &str) -> Option<Condition> {
+    let (negated, word) = match word.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, word),
+    };
+    let (key, value) = word.split_once(':')?;
+    let value = value.to_lowercase();
+    let qualifier = match key.to_lowercase().as_str() {
+        "repo" => Qualifier::Repo(value),
+        "status" => match value.as_str() {
+            "gone" => Qualifier::Status(RemoteStatus::Gone),
+            "pushed" => Qualifier::Status(RemoteStatus::Exists),
+            "unpushed" => Qualifier::Status(RemoteStatus::NeverPushed),
+            _ => Qualifier::Invalid,
+        },
+        "is" => match value.as_str() {
+            "dirty" => Qualifier::Dirty,
+            "clean" => Qualifier::Clean,
+            "pinned" => Qualifier::Pinned,
+            _ => Qualifier::Invalid,
+        },
+        "tag" => Qualifier::Tag(value),
+        "age" => parse_age(&value).unwrap_or(Qualifier::Invalid),
+        _ => return None,
+    };
+    Some(Condition { negated, qualifier })
+}
+
+/// Parses `>30d`, `<2w` or `12h`; without a comparison, older than.
+fn parse_age(value: &str) -> Option<Qualifier> {
+    let (older, value) = if let Some(rest) = value.strip_prefix('<') {
+        (false, rest)
+    } else {
+        (true, value.strip_prefix('>').unwrap_or(value))
+    };
+    let unit = match value.chars().last()? {
+        'h' => HOUR,
+        'd' => DAY,
+        'w' => WEEK,
+        _ => return None,
+    };
+    let amount: u64 = value[..value.len() - 1].parse().ok()?;
+    Some(Qualifier::Age {
+        older,
+        seconds: amount.checked_mul(unit)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let query = Query::parse("login repo:API !is:dirty age:>30d status:nope");
+        assert_eq!(query.words, ["login"]);
+        assert_eq!(
+            query.conditions,
+            [
+                Condition {
+                    negated: false,
+                    qualifier: Qualifier::Repo("api".to_string()),
+                },
+                Condition {
+                    negated: true,
+                    qualifier: Qualifier::Dirty,
+                },
+                Condition {
+                    negated: false,
+                    qualifier: Qualifier::Age {
+                        older: true,
+                        seconds: 30 * DAY,
+                    },
+                },
+                Condition {
+                    negated: false,
+                    qualifier: Qualifier::Invalid,
+                },
+            ]
+        );
+        assert_eq!(
+            Query::parse("age:>99999999999999999w").conditions[0].qualifier,
+            Qualifier::Invalid
+        );
+        // Unknown keys are fuzzy-matched like any other word.
+        assert_eq!(Query::parse("http://x").words, ["http://x"]);
+    }
+
+    #[test]
+    fn test_matches() {
+        let now = 100 * DAY;
+        let tags = vec!["Urgent".to_string()];
+        let facts = WorktreeFacts {
+            repo: "api-server",
+            remote_status: RemoteStatus::NeverPushed,
+            is_dirty: true,
+            pinned: false,
+            tags: &tags,
+            last_commit_time: Some((now - 40 * DAY) as i64),
+        };
+        for (input, expected) in [
+            ("is:dirty status:unpushed", true),
+            ("repo:api tag:urgent", true),
+            ("age:>30d", true),
+            ("age:<1w", false),
+            ("!is:dirty", false),
+            ("!status:gone is:dirty", true),
+            ("status:pushed", false),
+            ("status:nope", false),
+        ] {
+            assert_eq!(
+                Query::parse(input).matches(&facts, now),
+                expected,
+                "{}",
+                input
+            );
+        }
+    }
+}
@@ -5,12 +5,10 @@ use nucleo_matcher::{
 
 use super::list::ItemOrder;
 use crate::git::Repository;
+use crate::theme;
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
-    style::{
-        palette::tailwind::{GREEN, RED, SLATE},
-        Style, Stylize,
-    },
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{
         Block, BorderType, Clear, List, ListDirection, ListItem, ListState, Paragraph, Scrollbar,
@@ -22,7 +20,7 @@ use ratatui::{
 use super::{
     filter::FilterComponent,
     list::{Focus, ListComponent},
-    Action, EventState, SELECTED_STYLE,
+    Action, EventState,
 };
 use crate::keymap::InputMode;
 
@@ -32,6 +30,8 @@ pub struct RepositoriesComponent {
     state: ListState,
     selected_index: Option<usize>,
     focus: Focus,
+    /// Where the list was last drawn, to map mouse clicks to rows.
+    list_area: Rect,
 }
 
 impl RepositoriesComponent {
@@ -42,23 +42,26 @@ impl RepositoriesComponent {
             state: ListState::default().with_selected(Some(0)),
             selected_index: Some(0),
             focus: Focus::Filter,
+
+            list_area: Rect::default(),
         }
     }
 
     pub fn draw(&mut self, f: &mut Frame, rect: Rect, mode: InputMode) {
+        let theme = theme::current();
         f.render_widget(Clear, rect);
 
         let total = self.filtered_items().len();
         let title = {
             let mut spans = vec![
                 Span::raw(" "),
-                Span::styled("Repositories", Style::new().fg(GREEN.c400).bold()),
-                Span::styled(format!(" ({}) ", total), Style::new().fg(SLATE.c400)),
+                Span::styled("Repositories", Style::new().fg(theme.accent).bold()),
+                Span::styled(format!(" ({}) ", total), Style::new().fg(theme.text_dim)),
             ];
             if !self.filter.value.is_empty() && matches!(mode, InputMode::Normal) {
                 spans.push(Span::styled(
                     format!("/{} ", self.filter.value),
-                    Style::new().fg(SLATE.c500),
+                    Style::new().fg(theme.hint),
                 ));
             }
             Line::from(spans).alignment(Alignment::Center)
@@ -66,7 +69,7 @@ impl RepositoriesComponent {
 
         let mut block = Block::bordered()
             .border_type(BorderType::Rounded)
-            .border_style(super::POPUP_BORDER_STYLE)
+            .border_style(theme.popup_border())
             .title(title);
         if matches!(mode, InputMode::Normal) {
             block = block.title_bottom(repos_keybinding_hint());
@@ -87,8 +90,8 @@ impl RepositoriesComponent {
 
             f.render_widget(
                 Paragraph::new(Line::from(vec![
-                    Span::styled(" / ", Style::new().fg(GREEN.c300).bold()),
-                    Span::styled(self.filter.value.clone(), Style::new().white()),
+                    Span::styled(" / ", Style::new().fg(theme.accent_bright).bold()),
+                    Span::styled(self.filter.value.clone(), Style::new().fg(theme.text)),
                 ])),
                 filter_line,
             );
@@ -98,7 +101,7 @@ impl RepositoriesComponent {
             ));
             f.render_widget(
                 Paragraph::new("─".repeat(sep_line.width as usize))
-                    .style(Style::new().fg(SLATE.c700)),
+                    .style(Style::new().fg(theme.faint)),
                 sep_line,
             );
             list_area
@@ -112,20 +115,31 @@ impl RepositoriesComponent {
             .map(|r| ListItem::new(r.name()))
             .collect();
         let list = List::new(items)
-            .style(Style::new().white())
-            .highlight_style(SELECTED_STYLE)
+            .style(Style::new().fg(theme.text))
+            .highlight_style(theme.selected())
             .direction(ListDirection::TopToBottom);
+        self.list_area = list_area;
         StatefulWidget::render(list, list_area, f.buffer_mut(), &mut self.state);
 
         let mut scroll_state = ScrollbarState::new(total).position(self.state.offset());
         let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
             .begin_symbol(None)
             .end_symbol(None)
-            .thumb_style(Style::new().dark_gray())
-            .track_style(Style::new().dark_gray());
+            .thumb_style(Style::new().fg(theme.faint))
+            .track_style(Style::new().fg(theme.faint));
         f.render_stateful_widget(scrollbar, list_area, &mut scroll_state);
     }
 
+    /// Selects the row at the clicked terminal cell; returns whether a row was hit.
+    pub fn click(&mut self, column: u16, row: u16) -> bool {
+        let area = self.list_area;
+        self.select_at(area, column, row)
+    }
+
+    pub fn scroll(&mut self, down: bool) {
+        self.scroll_selection(down);
+    }
+
     pub fn handle_action(&mut self, action: Action) -> EventState {
         match action {
             Action::MoveDown => {
@@ -191,6 +205,10 @@ impl RepositoriesComponent {
         }
     }
 
+    pub fn repository_names(&self) -> Vec<String> {
+        self.repositories.iter().map(|r| r.name()).collect()
+    }
+
     pub fn add_repository(&mut self, repo: Repository) {
         self.repositories.push(repo);
     }
@@ -210,11 +228,12 @@ impl RepositoriesComponent {
 }
 
 fn repos_keybinding_hint() -> Line<'static> {
+    let theme = theme::current();
     Line::from(vec![
-        Span::styled("[Enter] ", Style::new().fg(GREEN.c400).bold()),
-        Span::styled("select", Style::new().fg(SLATE.c500)),
-        Span::styled("  [Esc] ", Style::new().fg(RED.c400).bold()),
-        Span::styled("close ", Style::new().fg(SLATE.c500)),
+        Span::styled("[Enter] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("select", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.danger).bold()),
+        Span::styled("close ", Style::new().fg(theme.hint)),
     ])
     .right_aligned()
 }
@@ -257,7 +276,7 @@ impl ListComponent<Repository> for RepositoriesComponent {
                 Some((r, total))
             })
             .collect();
-        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
         scored.into_iter().map(|(r, _)| r).collect()
     }
 
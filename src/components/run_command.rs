@@ -0,0 +1,304 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget},
+    Frame,
+};
+
+use super::{filter::FilterComponent, Action, EventState};
+use crate::theme;
+
+/// Lines kept before the oldest are dropped.
+const MAX_LINES: usize = 10_000;
+
+/// A command typed for the marked worktrees, then its output as it runs in
+/// each of them.
+pub struct RunCommandComponent {
+    input: FilterComponent,
+    paths: Vec<String>,
+    /// The command, once it runs.
+    command: Option<String>,
+    /// Width of the widest worktree label, so the output lines up.
+    label_width: usize,
+    lines: Vec<Line<'static>>,
+    passed: usize,
+    failed: usize,
+    running: bool,
+    /// First line shown; `None` follows the output.
+    top: Option<usize>,
+    /// Lines that fit in the panel when it was last drawn.
+    page: usize,
+}
+
+impl RunCommandComponent {
+    /// Height of the prompt, including its borders.
+    pub const PROMPT_HEIGHT: u16 = 8;
+
+    pub fn new(paths: Vec<String>) -> Self {
+        Self {
+            input: FilterComponent::new(),
+            paths,
+            command: None,
+            label_width: 0,
+            lines: vec![],
+            passed: 0,
+            failed: 0,
+            running: false,
+            top: None,
+            page: 0,
+        }
+    }
+
+    /// The worktrees the command runs in.
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    pub fn input(&self) -> &str {
+        self.input.value.trim()
+    }
+
+    /// Switches from the prompt to the output of the command, which runs in
+    /// the worktrees with `labels`.
+    pub fn start(&mut self, labels: &[String]) {
+        let command = self.input().to_string();
+        let theme = theme::current();
+        self.label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        self.lines = vec![Line::from(vec![
+            Span::styled("$ ", Style::new().fg(theme.hint)),
+            Span::styled(command.clone(), Style::new().fg(theme.info).bold()),
+            Span::styled(
+                format!("  in {} worktree(s)", labels.len()),
+                Style::new().fg(theme.hint),
+            ),
+        ])];
+        self.command = Some(command);
+        self.running = true;
+    }
+
+    pub fn push_output(&mut self, label: &str, line: String) {
+        let theme = theme::current();
+        self.push(Line::from(vec![
+            Span::styled(
+                format!("{:<width$}", label, width = self.label_width),
+                Style::new().fg(theme.accent),
+            ),
+            Span::styled(" │ ", Style::new().fg(theme.hint)),
+            Span::styled(line, Style::new().fg(theme.text_muted)),
+        ]));
+    }
+
+    pub fn push_result(&mut self, label: String, result: Result<(), String>) {
+        let theme = theme::current();
+        let line = match result {
+            Ok(()) => {
+                self.passed += 1;
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", theme.glyphs.success),
+                        Style::new().fg(theme.accent),
+                    ),
+                    Span::styled(label, Style::new().fg(theme.text)),
+                    Span::styled("  passed", Style::new().fg(theme.hint)),
+                ])
+            }
+            Err(error) => {
+                self.failed += 1;
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", theme.glyphs.failure),
+                        Style::new().fg(theme.danger),
+                    ),
+                    Span::styled(label, Style::new().fg(theme.text)),
+                    Span::styled(format!("  {}", error), Style::new().fg(theme.danger_soft)),
+                ])
+            }
+        };
+        self.push(line);
+    }
+
+    /// Marks the command as finished everywhere and returns how often it
+    /// passed and failed.
+    pub fn finish(&mut self) -> (usize, usize) {
+        let theme = theme::current();
+        self.running = false;
+        self.push(Line::from(Span::styled(
+            format!("{} passed, {} failed", self.passed, self.failed),
+            Style::new().fg(theme.accent_bright).bold(),
+        )));
+        (self.passed, self.failed)
+    }
+
+    pub fn command(&self) -> Option<&str> {
+        self.command.as_deref()
+    }
+
+    fn push(&mut self, line: Line<'static>) {
+        self.lines.push(line);
+        if self.lines.len() > MAX_LINES {
+            let dropped = self.lines.len() - MAX_LINES;
+            self.lines.drain(..dropped);
+            self.top = self.top.map(|top| top.saturating_sub(dropped));
+        }
+    }
+
+    pub fn draw_prompt(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        frame.render_widget(Clear, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.popup_border())
+            .title(Line::from(" Run a Command ").style(Style::new().fg(theme.accent_bright).bold()))
+            .title_bottom(hint(&[("Enter", "run"), ("Esc", "cancel")]));
+        let inner = block.inner(area);
+        block.render(area, frame.buffer_mut());
+
+        let [_, label_area, input_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+        ])
+        .horizontal_margin(4)
+        .areas(inner);
+
+        let count = match self.paths.len() {
+            1 => "the worktree".to_string(),
+            n => format!("each of the {} worktrees", n),
+        };
+        Paragraph::new(format!("Shell command to run in {}:", count))
+            .style(Style::new().fg(theme.text_muted))
+            .render(label_area, frame.buffer_mut());
+        Paragraph::new(self.input.value.as_str())
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .border_style(theme.active_border())
+                    .padding(Padding::horizontal(1)),
+            )
+            .render(input_area, frame.buffer_mut());
+
+        // input_area: border(1) + padding(1) = offset 2; y+1 skips top border row
+        frame.set_cursor_position((
+            input_area.x + 2 + self.input.cursor_pos() as u16,
+            input_area.y + 1,
+        ));
+    }
+
+    pub fn draw_output(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        frame.render_widget(Clear, area);
+
+        let status = if self.running {
+            Span::styled(
+                format!("running, {} done ", self.passed + self.failed),
+                Style::new().fg(theme.warning),
+            )
+        } else {
+            Span::styled(
+                format!("{} passed, {} failed ", self.passed, self.failed),
+                Style::new().fg(if self.failed > 0 {
+                    theme.danger
+                } else {
+                    theme.accent
+                }),
+            )
+        };
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.popup_border())
+            .title(Line::from(vec![
+                Span::styled(" Output ", Style::new().fg(theme.accent_bright).bold()),
+                status,
+            ]))
+            .title_bottom(hint(&[
+                ("j/k", "scroll"),
+                ("g/G", "top/follow"),
+                ("Esc", "close"),
+            ]));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        self.page = inner.height as usize;
+        let last_top = self.lines.len().saturating_sub(self.page);
+        let top = self.top.map_or(last_top, |top| top.min(last_top));
+        let lines: Vec<Line> = self.lines[top..].iter().take(self.page).cloned().collect();
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    pub fn handle_action(&mut self, action: Action) -> EventState {
+        if self.command.is_none() {
+            match action {
+                Action::InsertChar(c) => self.input.enter_char(c),
+                Action::DeleteChar => self.input.delete_char(),
+                _ => return EventState::NotConsumed,
+            }
+            return EventState::Consumed;
+        }
+        match action {
+            Action::MoveDown => self.scroll(true),
+            Action::MoveUp => self.scroll(false),
+            Action::GoFirst => self.top = Some(0),
+            Action::GoLast => self.top = None,
+            _ => return EventState::NotConsumed,
+        }
+        EventState::Consumed
+    }
+
+    /// Scrolls a line; scrolling down to the end follows the output again.
+    pub fn scroll(&mut self, down: bool) {
+        let last_top = self.lines.len().saturating_sub(self.page);
+        let top = self.top.unwrap_or(last_top);
+        let top = if down {
+            (top + 1).min(last_top)
+        } else {
+            top.saturating_sub(1)
+        };
+        self.top = (top < last_top).then_some(top);
+    }
+}
+
+fn hint(keys: &[(&'static str, &'static str)]) -> Line<'static> {
+    let theme = theme::current();
+    let mut spans = vec![];
+    for (key, label) in keys {
+        spans.push(Span::styled(
+            format!(" [{}] ", key),
+            Style::new().fg(theme.accent).bold(),
+        ));
+        spans.push(Span::styled(*label, Style::new().fg(theme.hint)));
+    }
+    spans.push(Span::raw(" "));
+    Line::from(spans).right_aligned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_follows_the_output_at_the_end() {
+        let mut component = RunCommandComponent::new(vec!["/wt/api".to_string()]);
+        for c in "make".chars() {
+            component.handle_action(Action::InsertChar(c));
+        }
+        component.start(&["api/main".to_string()]);
+        assert_eq!(component.command(), Some("make"));
+        component.page = 2;
+        for i in 0..5 {
+            component.push_output("api/main", i.to_string());
+        }
+
+        component.handle_action(Action::MoveUp);
+        assert_eq!(component.top, Some(3));
+        component.push_output("api/main", "more".to_string());
+        assert_eq!(component.top, Some(3));
+        component.handle_action(Action::GoFirst);
+        assert_eq!(component.top, Some(0));
+        for _ in 0..10 {
+            component.handle_action(Action::MoveDown);
+        }
+        assert_eq!(component.top, None);
+    }
+}
@@ -1,9 +1,6 @@
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{
-        palette::tailwind::{GREEN, RED, SLATE},
-        Style, Stylize,
-    },
+    style::{Style, Stylize},
     text::{Line, Span},
     widgets::{
         Block, BorderType, Clear, List, ListDirection, ListItem, ListState, Scrollbar,
@@ -14,13 +11,16 @@ use ratatui::{
 
 use super::{
     list::{ItemOrder, ListComponent},
-    Action, EventState, POPUP_BORDER_STYLE, SELECTED_STYLE,
+    Action, EventState,
 };
+use crate::theme;
 
 pub struct SelectDirectoryComponent {
     pub dirs: Vec<String>,
     state: ListState,
     selected_index: usize,
+    /// Where the list was last drawn, to map mouse clicks to rows.
+    list_area: Rect,
 }
 
 impl SelectDirectoryComponent {
@@ -29,6 +29,8 @@ impl SelectDirectoryComponent {
             dirs,
             state: ListState::default().with_selected(Some(0)),
             selected_index: 0,
+
+            list_area: Rect::default(),
         }
     }
 
@@ -37,17 +39,18 @@ impl SelectDirectoryComponent {
     }
 
     pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
         frame.render_widget(Clear, area);
 
         let title = Line::from(vec![Span::styled(
             " Select Clone Directory ",
-            Style::new().fg(GREEN.c400).bold(),
+            Style::new().fg(theme.accent).bold(),
         )])
         .alignment(Alignment::Center);
 
         let block = Block::bordered()
             .border_type(BorderType::Rounded)
-            .border_style(POPUP_BORDER_STYLE)
+            .border_style(theme.popup_border())
             .title(title)
             .title_bottom(dir_keybinding_hint());
 
@@ -61,20 +64,31 @@ impl SelectDirectoryComponent {
             .map(|d| ListItem::new(d.as_str()))
             .collect();
         let list = List::new(items)
-            .style(Style::new().white())
-            .highlight_style(SELECTED_STYLE)
+            .style(Style::new().fg(theme.text))
+            .highlight_style(theme.selected())
             .direction(ListDirection::TopToBottom);
+        self.list_area = inner_area;
         StatefulWidget::render(list, inner_area, frame.buffer_mut(), &mut self.state);
 
         let mut scroll_state = ScrollbarState::new(total).position(self.state.offset());
         let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
             .begin_symbol(None)
             .end_symbol(None)
-            .thumb_style(Style::new().dark_gray())
-            .track_style(Style::new().dark_gray());
+            .thumb_style(Style::new().fg(theme.faint))
+            .track_style(Style::new().fg(theme.faint));
         frame.render_stateful_widget(scrollbar, inner_area, &mut scroll_state);
     }
 
+    /// Selects the row at the clicked terminal cell; returns whether a row was hit.
+    pub fn click(&mut self, column: u16, row: u16) -> bool {
+        let area = self.list_area;
+        self.select_at(area, column, row)
+    }
+
+    pub fn scroll(&mut self, down: bool) {
+        self.scroll_selection(down);
+    }
+
     pub fn handle_action(&mut self, action: Action) -> EventState {
         match action {
             Action::MoveDown => {
@@ -99,11 +113,12 @@ impl SelectDirectoryComponent {
 }
 
 fn dir_keybinding_hint() -> Line<'static> {
+    let theme = theme::current();
     Line::from(vec![
-        Span::styled("[Enter] ", Style::new().fg(GREEN.c400).bold()),
-        Span::styled("select", Style::new().fg(SLATE.c500)),
-        Span::styled("  [Esc] ", Style::new().fg(RED.c400).bold()),
-        Span::styled("cancel ", Style::new().fg(SLATE.c500)),
+        Span::styled("[Enter] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("select", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.danger).bold()),
+        Span::styled("cancel ", Style::new().fg(theme.hint)),
     ])
     .right_aligned()
 }
@@ -0,0 +1,126 @@
+use ratatui::{
+    layout::{Margin, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Paragraph},
+    Frame,
+};
+
+use super::{Action, EventState};
+use crate::theme;
+
+/// Outcome of an operation on one worktree: a short message on success, the
+/// error otherwise.
+pub type Outcome = (String, Result<String, String>);
+
+/// Per-worktree results of an operation applied to several worktrees.
+pub struct SummaryComponent {
+    pub title: String,
+    pub outcomes: Vec<Outcome>,
+    scroll: u16,
+}
+
+impl SummaryComponent {
+    pub fn new(title: String, outcomes: Vec<Outcome>) -> Self {
+        Self {
+            title,
+            outcomes,
+            scroll: 0,
+        }
+    }
+
+    pub fn height(&self) -> u16 {
+        // borders (2) + vertical margin (2*1)
+        self.outcomes.len().min(20) as u16 + 4
+    }
+
+    pub fn failures(&self) -> usize {
+        self.outcomes.iter().filter(|(_, r)| r.is_err()).count()
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        frame.render_widget(Clear, area);
+
+        let failures = self.failures();
+        let title = Line::from(vec![
+            Span::styled(
+                format!(" {} ", self.title),
+                Style::new().fg(theme.accent_bright).bold(),
+            ),
+            Span::styled(
+                format!("{} ok", self.outcomes.len() - failures),
+                Style::new().fg(theme.accent),
+            ),
+            Span::raw(", "),
+            Span::styled(
+                format!("{} failed ", failures),
+                Style::new().fg(if failures > 0 {
+                    theme.danger
+                } else {
+                    theme.hint
+                }),
+            ),
+        ]);
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.popup_border())
+            .title(title)
+            .title_bottom(keybinding_hint());
+        frame.render_widget(block, area);
+
+        let lines: Vec<Line> = self
+            .outcomes
+            .iter()
+            .map(|(label, result)| match result {
+                Ok(message) => Line::from(vec![
+                    Span::styled(
+                        format!("{} ", theme.glyphs.success),
+                        Style::new().fg(theme.accent),
+                    ),
+                    Span::styled(label.clone(), Style::new().fg(theme.text)),
+                    Span::styled(format!("  {}", message), Style::new().fg(theme.hint)),
+                ]),
+                Err(error) => Line::from(vec![
+                    Span::styled(
+                        format!("{} ", theme.glyphs.failure),
+                        Style::new().fg(theme.danger),
+                    ),
+                    Span::styled(label.clone(), Style::new().fg(theme.text)),
+                    Span::styled(format!("  {}", error), Style::new().fg(theme.danger_soft)),
+                ]),
+            })
+            .collect();
+        let inner = area.inner(Margin {
+            horizontal: 2,
+            vertical: 1,
+        });
+        frame.render_widget(Paragraph::new(lines).scroll((self.scroll, 0)), inner);
+    }
+
+    pub fn handle_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::MoveDown => {
+                let last = self.outcomes.len().saturating_sub(1) as u16;
+                self.scroll = (self.scroll + 1).min(last);
+                EventState::Consumed
+            }
+            Action::MoveUp => {
+                self.scroll = self.scroll.saturating_sub(1);
+                EventState::Consumed
+            }
+            _ => EventState::NotConsumed,
+        }
+    }
+}
+
+fn keybinding_hint() -> Line<'static> {
+    let theme = theme::current();
+    Line::from(vec![
+        Span::styled("[j/k] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("scroll", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("close ", Style::new().fg(theme.hint)),
+    ])
+    .right_aligned()
+}
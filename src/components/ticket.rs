@@ -0,0 +1,155 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget, Wrap},
+    Frame,
+};
+
+use super::{Action, EventState};
+use crate::theme;
+
+/// Popup asking for the issue to start a worktree from.
+pub struct TicketComponent {
+    character_index: usize,
+    pub input: String,
+    pub error: Option<String>,
+}
+
+impl TicketComponent {
+    pub fn new() -> Self {
+        Self {
+            character_index: 0,
+            input: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn current_input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn set_error(&mut self, msg: String) {
+        self.error = Some(msg);
+    }
+
+    pub fn reset(&mut self) {
+        self.input.clear();
+        self.character_index = 0;
+        self.error = None;
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = theme::current();
+        frame.render_widget(Clear, area);
+
+        let outer_block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.popup_border())
+            .title(
+                Line::from(" Worktree from Ticket ")
+                    .style(Style::new().fg(theme.accent_bright).bold()),
+            )
+            .title_bottom(keybinding_hint());
+
+        let inner_area = outer_block.inner(area);
+        outer_block.render(area, frame.buffer_mut());
+
+        let [_, label_area, input_area, status_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(2),
+        ])
+        .horizontal_margin(4)
+        .areas(inner_area);
+
+        Paragraph::new("Issue key or link:")
+            .style(Style::new().fg(theme.text_muted))
+            .render(label_area, frame.buffer_mut());
+
+        Paragraph::new(self.input.as_str())
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .border_style(theme.active_border())
+                    .padding(Padding::horizontal(1)),
+            )
+            .render(input_area, frame.buffer_mut());
+
+        if let Some(err) = &self.error {
+            // Errors end with what to do about them, so let them wrap rather than cut off.
+            Paragraph::new(err.as_str())
+                .style(Style::new().fg(theme.danger))
+                .wrap(Wrap { trim: true })
+                .render(status_area, frame.buffer_mut());
+        }
+
+        // input_area: border(1) + padding(1) = offset 2; y+1 skips top border row
+        frame.set_cursor_position((
+            input_area.x + 2 + self.character_index as u16,
+            input_area.y + 1,
+        ));
+    }
+
+    pub fn handle_action(&mut self, action: Action) -> EventState {
+        match action {
+            Action::InsertChar(c) => {
+                self.enter_char(c);
+                EventState::Consumed
+            }
+            Action::DeleteChar => {
+                self.delete_char();
+                EventState::Consumed
+            }
+            _ => EventState::NotConsumed,
+        }
+    }
+
+    fn enter_char(&mut self, c: char) {
+        let index = self.byte_index();
+        self.input.insert(index, c);
+        self.move_cursor_right();
+        self.error = None;
+    }
+
+    fn delete_char(&mut self) {
+        if self.character_index != 0 {
+            let current_index = self.character_index;
+            let before = self.input.chars().take(current_index - 1);
+            let after = self.input.chars().skip(current_index);
+            self.input = before.chain(after).collect();
+            self.move_cursor_left();
+            self.error = None;
+        }
+    }
+
+    fn byte_index(&self) -> usize {
+        self.input
+            .char_indices()
+            .map(|(i, _)| i)
+            .nth(self.character_index)
+            .unwrap_or(self.input.len())
+    }
+
+    fn move_cursor_right(&mut self) {
+        let moved = self.character_index.saturating_add(1);
+        self.character_index = moved.clamp(0, self.input.chars().count());
+    }
+
+    fn move_cursor_left(&mut self) {
+        let moved = self.character_index.saturating_sub(1);
+        self.character_index = moved.clamp(0, self.input.chars().count());
+    }
+}
+
+fn keybinding_hint() -> Line<'static> {
+    let theme = theme::current();
+    Line::from(vec![
+        Span::styled("[Enter] ", Style::new().fg(theme.accent).bold()),
+        Span::styled("fetch issue", Style::new().fg(theme.hint)),
+        Span::styled("  [Esc] ", Style::new().fg(theme.danger).bold()),
+        Span::styled("cancel ", Style::new().fg(theme.hint)),
+    ])
+    .right_aligned()
+}
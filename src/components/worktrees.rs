@@ -1,15 +1,11 @@
 use crate::git::{self, RemoteStatus};
+use crate::github::{CiStatus, PrState, PrStatus, ReviewDecision};
+use crate::history::{self, SortMode, Usage};
+use crate::metadata::WorktreeMetadata;
 use color_eyre::eyre;
-use nucleo_matcher::{
-    pattern::{CaseMatching, Normalization, Pattern},
-    Config, Matcher, Utf32Str,
-};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
-    style::{
-        palette::tailwind::{AMBER, BLUE, GREEN, RED, SLATE},
-        Color, Modifier, Style, Stylize,
-    },
+    style::{Modifier, Style, Stylize},
     text::{Line, Span},
     widgets::{
         Block, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
@@ -18,12 +14,28 @@ use ratatui::{
     Frame,
 };
 
-use super::{filter::FilterComponent, Action, EventState};
+use super::list::{Focus, ItemOrder, ListComponent};
 use super::{
-    list::{Focus, ItemOrder, ListComponent},
-    SELECTED_STYLE,
+    filter::FilterComponent,
+    query::{Query, WordMatcher, WorktreeFacts},
+    Action, EventState,
 };
 use crate::keymap::InputMode;
+use crate::theme;
+use std::collections::{HashMap, HashSet};
+
+/// A line of the list: a worktree, or in the grouped view the header of a
+/// repository.
+enum Row {
+    Repository {
+        name: String,
+        collapsed: bool,
+        /// Indexes into `filtered_items()`, also when collapsed.
+        worktrees: Vec<usize>,
+    },
+    /// Index into `filtered_items()`.
+    Worktree(usize),
+}
 
 pub struct WorktreesComponent {
     worktrees: Vec<git::Worktree>,
@@ -33,11 +45,38 @@ pub struct WorktreesComponent {
     selected_index: Option<usize>,
     pub last_error: Option<String>,
     worktrees_dir: String,
+    /// PR status per worktree path, filled in by the background refresh.
+    pr_statuses: HashMap<String, PrStatus>,
+    /// Tags, tickets and notes per worktree path.
+    metadata: HashMap<String, WorktreeMetadata>,
+    /// Order of the list when no filter is typed.
+    sort_mode: SortMode,
+    /// How often and how recently each worktree path was selected.
+    usage: HashMap<String, Usage>,
+    /// Whether worktrees are shown under a header per repository.
+    grouped: bool,
+    /// Repositories whose worktrees are hidden in the grouped view.
+    collapsed: HashSet<String>,
+    /// Repository name per worktree path, to group without opening the
+    /// repositories on every draw.
+    repository_names: HashMap<String, String>,
+    /// Paths of the worktrees marked for a batch operation.
+    marked: HashSet<String>,
+    /// Row where the visual range started, while one is being selected.
+    visual_anchor: Option<usize>,
+    /// Paths of the worktrees with a live tmux or zellij session.
+    sessions: HashSet<String>,
+    /// Where the list was last drawn, to map mouse clicks to rows.
+    list_area: Rect,
 }
 
 impl WorktreesComponent {
     pub fn new(worktrees: Vec<git::Worktree>, worktrees_dir: String) -> WorktreesComponent {
         let selected_index = if worktrees.is_empty() { None } else { Some(0) };
+        let repository_names = worktrees
+            .iter()
+            .map(|wt| (wt.path().to_string(), repository_name_of(wt)))
+            .collect();
         Self {
             filter: FilterComponent::new(),
             state: ListState::default().with_selected(selected_index),
@@ -46,37 +85,144 @@ impl WorktreesComponent {
             worktrees_dir: worktrees_dir.trim_end_matches('/').to_string(),
             worktrees,
             last_error: None,
+            pr_statuses: HashMap::new(),
+            metadata: HashMap::new(),
+            sort_mode: SortMode::default(),
+            usage: HashMap::new(),
+            grouped: false,
+            collapsed: HashSet::new(),
+            repository_names,
+            marked: HashSet::new(),
+            visual_anchor: None,
+            sessions: HashSet::new(),
+
+            list_area: Rect::default(),
         }
     }
 
     pub fn draw(&mut self, f: &mut Frame, rect: Rect, mode: InputMode, is_active: bool) {
+        let theme = theme::current();
         let worktrees_dir = self.worktrees_dir.clone();
 
         // Collect display data — ends the filtered_items() borrow before we need &self again.
-        let display_data: Vec<(RemoteStatus, bool, String)> = {
+        let display_data: Vec<(RemoteStatus, bool, String, String)> = {
             let filtered = self.filtered_items();
             filtered
                 .iter()
-                .map(|wt| (wt.remote_status, wt.is_dirty, wt.path().to_string()))
+                .map(|wt| {
+                    (
+                        wt.remote_status,
+                        wt.is_dirty,
+                        wt.path().to_string(),
+                        wt.branch.clone(),
+                    )
+                })
                 .collect()
         };
+        let rows = self.rows();
         let total = display_data.len();
-        let items: Vec<ListItem<'static>> = display_data
+        // Pinned worktrees come first; mark them, and indent the rest to match.
+        let any_pinned = display_data
+            .iter()
+            .any(|(_, _, path, _)| self.is_pinned(path));
+        let mut marked = self.marked.clone();
+        marked.extend(self.visual_range_paths());
+        let lines: Vec<(Line<'static>, Option<&PrStatus>)> = display_data
+            .iter()
+            .map(|(remote_status, is_dirty, path, branch)| {
+                let mut line = worktree_line(
+                    *remote_status,
+                    *is_dirty,
+                    path,
+                    branch,
+                    &worktrees_dir,
+                    !self.grouped,
+                );
+                if self.is_pinned(path) {
+                    line.spans.insert(
+                        0,
+                        Span::styled(
+                            format!("{} ", theme.glyphs.pinned),
+                            Style::default().fg(theme.warning_soft),
+                        ),
+                    );
+                } else if any_pinned {
+                    line.spans.insert(0, Span::raw("  "));
+                }
+                if marked.contains(path) {
+                    line.spans.insert(
+                        0,
+                        Span::styled(
+                            format!("{} ", theme.glyphs.marked),
+                            Style::default().fg(theme.info_soft),
+                        ),
+                    );
+                } else if !marked.is_empty() {
+                    line.spans.insert(0, Span::raw("  "));
+                }
+                if self.grouped {
+                    line.spans.insert(0, Span::raw("  "));
+                }
+                if self.sessions.contains(path) {
+                    line.spans.push(Span::styled(
+                        format!(" {}", theme.glyphs.session),
+                        Style::default().fg(theme.info),
+                    ));
+                }
+                if let Some(metadata) = self.metadata.get(path) {
+                    line.spans.extend(metadata_spans(metadata));
+                }
+                (line, self.pr_statuses.get(path))
+            })
+            .collect();
+        // Pad every name to the widest one so the PR columns line up.
+        let name_width = lines
+            .iter()
+            .map(|(line, _)| line.width())
+            .max()
+            .unwrap_or(0);
+        let items: Vec<ListItem<'static>> = rows
             .iter()
-            .map(|(remote_status, is_dirty, path)| {
-                worktree_to_list_item(*remote_status, *is_dirty, path, &worktrees_dir)
+            .map(|row| match row {
+                Row::Repository {
+                    name,
+                    collapsed,
+                    worktrees,
+                } => {
+                    let statuses: Vec<(RemoteStatus, bool)> = worktrees
+                        .iter()
+                        .map(|&i| (display_data[i].0, display_data[i].1))
+                        .collect();
+                    ListItem::new(repository_header(name, *collapsed, &statuses))
+                }
+                Row::Worktree(i) => {
+                    let (line, pr_status) = &lines[*i];
+                    worktree_to_list_item(line.clone(), *pr_status, name_width)
+                }
             })
             .collect();
 
         // B: cap current to total so a stale selected_index never shows x > y in (x/y)
-        let current = self.selected_index.map(|i| (i + 1).min(total)).unwrap_or(0);
+        // Repository headers are not counted.
+        let current = self
+            .selected_index
+            .map(|i| {
+                rows.iter()
+                    .take(i + 1)
+                    .filter(|row| matches!(row, Row::Worktree(_)))
+                    .count()
+                    .min(total)
+            })
+            .unwrap_or(0);
 
         let mode_indicator = match mode {
-            InputMode::Normal => Line::from(" NORMAL ").style(Style::new().fg(GREEN.c400).bold()),
-            InputMode::Insert => Line::from(" INSERT ").style(Style::new().fg(AMBER.c300).bold()),
+            InputMode::Normal => Line::from(" NORMAL ").style(Style::new().fg(theme.accent).bold()),
+            InputMode::Insert => {
+                Line::from(" INSERT ").style(Style::new().fg(theme.warning_soft).bold())
+            }
         };
         let bottom_left = match &self.last_error {
-            Some(err) => Line::from(format!(" {} ", err)).red().bold(),
+            Some(err) => Line::from(format!(" {} ", err)).fg(theme.danger).bold(),
             None => mode_indicator,
         };
 
@@ -84,16 +230,34 @@ impl WorktreesComponent {
         let title = {
             let mut spans = vec![
                 Span::raw(" "),
-                Span::styled("Worktrees", Style::new().fg(GREEN.c400).bold()),
+                Span::styled("Worktrees", Style::new().fg(theme.accent).bold()),
                 Span::styled(
                     format!(" ({}/{}) ", current, total),
-                    Style::new().fg(SLATE.c400),
+                    Style::new().fg(theme.text_dim),
                 ),
             ];
+            if self.visual_anchor.is_some() {
+                spans.push(Span::styled(
+                    "VISUAL ",
+                    Style::new().fg(theme.special).bold(),
+                ));
+            }
+            if !marked.is_empty() {
+                spans.push(Span::styled(
+                    format!("{} marked ", marked.len()),
+                    Style::new().fg(theme.info_soft),
+                ));
+            }
+            if self.sort_mode != SortMode::Alphabetical {
+                spans.push(Span::styled(
+                    format!("↓{} ", self.sort_mode.label()),
+                    Style::new().fg(theme.info_soft),
+                ));
+            }
             if !self.filter.value.is_empty() && matches!(mode, InputMode::Normal) {
                 spans.push(Span::styled(
                     format!("/{} ", self.filter.value),
-                    Style::new().fg(SLATE.c500),
+                    Style::new().fg(theme.hint),
                 ));
             }
             Line::from(spans)
@@ -101,16 +265,16 @@ impl WorktreesComponent {
 
         let mut block = Block::bordered()
             .border_type(ratatui::widgets::BorderType::Rounded)
-            .border_style(super::BORDER_STYLE)
+            .border_style(theme.border())
             .title(title)
             .title_bottom(bottom_left);
 
-        // C: style ? help hint with AMBER.c300 to match the rest of the keybinding palette
+        // C: style the ? help hint like the keybinding hints of the popups
         if matches!(mode, InputMode::Normal) {
             block = block.title_bottom(
                 Line::from(vec![
-                    Span::styled(" ? ", Style::new().fg(BLUE.c400).bold()),
-                    Span::styled("help ", Style::new().fg(SLATE.c500)),
+                    Span::styled(" ? ", Style::new().fg(theme.info).bold()),
+                    Span::styled("help ", Style::new().fg(theme.hint)),
                 ])
                 .right_aligned(),
             );
@@ -134,8 +298,8 @@ impl WorktreesComponent {
 
             f.render_widget(
                 Paragraph::new(Line::from(vec![
-                    Span::styled(" / ", Style::new().fg(GREEN.c300).bold()),
-                    Span::styled(self.filter.value.clone(), Style::new().white()),
+                    Span::styled(" / ", Style::new().fg(theme.accent_bright).bold()),
+                    Span::styled(self.filter.value.clone(), Style::new().fg(theme.text)),
                 ])),
                 filter_line,
             );
@@ -146,7 +310,7 @@ impl WorktreesComponent {
             ));
             f.render_widget(
                 Paragraph::new("─".repeat(sep_line.width as usize))
-                    .style(Style::new().fg(SLATE.c700)),
+                    .style(Style::new().fg(theme.faint)),
                 sep_line,
             );
             list_area
@@ -155,18 +319,29 @@ impl WorktreesComponent {
         };
 
         let list = List::new(items)
-            .style(Style::new().white())
-            .highlight_style(SELECTED_STYLE)
+            .style(Style::new().fg(theme.text))
+            .highlight_style(theme.selected())
             .direction(ratatui::widgets::ListDirection::TopToBottom);
+        self.list_area = list_area;
         StatefulWidget::render(list, list_area, f.buffer_mut(), &mut self.state);
 
-        let mut scroll_state = ScrollbarState::new(total).position(self.state.offset());
+        let mut scroll_state = ScrollbarState::new(rows.len()).position(self.state.offset());
         let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
             .begin_symbol(None)
             .end_symbol(None);
         f.render_stateful_widget(scrollbar, list_area, &mut scroll_state);
     }
 
+    /// Selects the row at the clicked terminal cell; returns whether a row was hit.
+    pub fn click(&mut self, column: u16, row: u16) -> bool {
+        let area = self.list_area;
+        self.select_at(area, column, row)
+    }
+
+    pub fn scroll(&mut self, down: bool) {
+        self.scroll_selection(down);
+    }
+
     pub fn handle_action(&mut self, action: Action) -> EventState {
         match action {
             Action::MoveDown => {
@@ -189,17 +364,50 @@ impl WorktreesComponent {
                 if self.selected_worktree_path().is_some() {
                     EventState::Exit
                 } else {
+                    if let Some(repo) = self.selected_repository_header() {
+                        let collapsed = self.collapsed.contains(&repo);
+                        self.set_collapsed(repo, !collapsed);
+                    }
                     EventState::Consumed
                 }
             }
+            Action::Collapse => {
+                let repo = self.selected_repository_header().or_else(|| {
+                    let path = self.selected_worktree_path()?;
+                    self.grouped.then(|| self.repository_of(&path))
+                });
+                if let Some(repo) = repo {
+                    self.set_collapsed(repo, true);
+                }
+                EventState::Consumed
+            }
+            Action::Expand => {
+                if let Some(repo) = self.selected_repository_header() {
+                    self.set_collapsed(repo, false);
+                }
+                EventState::Consumed
+            }
+            Action::ToggleMark => {
+                self.toggle_mark();
+                self.select(ItemOrder::Next);
+                EventState::Consumed
+            }
+            Action::ToggleVisual => {
+                self.toggle_visual();
+                EventState::Consumed
+            }
+            Action::ToggleMarkAll => {
+                self.toggle_mark_all();
+                EventState::Consumed
+            }
             Action::InsertChar(c) => {
                 self.filter.enter_char(c);
-                self.select(ItemOrder::First);
+                self.select_first_worktree();
                 EventState::Consumed
             }
             Action::DeleteChar => {
                 self.filter.delete_char();
-                self.select(ItemOrder::First);
+                self.select_first_worktree();
                 EventState::Consumed
             }
             _ => EventState::NotConsumed,
@@ -225,69 +433,367 @@ impl WorktreesComponent {
         matches!(self.focus, Focus::Filter)
     }
 
+    pub fn sort_mode(&self) -> SortMode {
+        self.sort_mode
+    }
+
+    /// Re-sorts the list and selects its first worktree.
+    pub fn set_sort_mode(&mut self, sort_mode: SortMode) {
+        self.sort_mode = sort_mode;
+        self.select_first_worktree();
+    }
+
+    pub fn is_grouped(&self) -> bool {
+        self.grouped
+    }
+
+    /// Switches between the flat list and the view grouped by repository,
+    /// keeping the selected worktree selected.
+    pub fn set_grouped(&mut self, grouped: bool) {
+        let selected_path = self.selected_worktree_path();
+        self.grouped = grouped;
+        if !selected_path.is_some_and(|path| self.select_path(&path)) {
+            self.select_first_worktree();
+        }
+    }
+
+    /// Marks the selected worktree, or unmarks it if it is marked.
+    pub fn toggle_mark(&mut self) {
+        if let Some(path) = self.selected_worktree_path() {
+            if !self.marked.remove(&path) {
+                self.marked.insert(path);
+            }
+        }
+    }
+
+    /// Starts marking a range from the selected row, or marks the range
+    /// selected so far.
+    pub fn toggle_visual(&mut self) {
+        if self.visual_anchor.is_some() {
+            let paths = self.visual_range_paths();
+            self.marked.extend(paths);
+            self.visual_anchor = None;
+        } else {
+            self.visual_anchor = self.selected_index;
+        }
+    }
+
+    /// Marks every worktree that matches the filter, or unmarks them all if
+    /// they already are.
+    pub fn toggle_mark_all(&mut self) {
+        let paths: Vec<String> = self
+            .filtered_items()
+            .iter()
+            .map(|wt| wt.path().to_string())
+            .collect();
+        if paths.iter().all(|path| self.marked.contains(path)) {
+            for path in &paths {
+                self.marked.remove(path);
+            }
+        } else {
+            self.marked.extend(paths);
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.visual_anchor = None;
+    }
+
+    /// Paths a batch operation applies to: the marked worktrees, including a
+    /// visual range in progress, in list order, or else the selected one.
+    pub fn target_paths(&mut self) -> Vec<String> {
+        let mut marked = self.marked.clone();
+        marked.extend(self.visual_range_paths());
+        if marked.is_empty() {
+            return self.selected_worktree_path().into_iter().collect();
+        }
+        let mut paths: Vec<String> = self
+            .filtered_items()
+            .iter()
+            .map(|wt| wt.path().to_string())
+            .filter(|path| marked.contains(path))
+            .collect();
+        // Marked worktrees hidden by the filter still count.
+        let mut hidden: Vec<String> = self
+            .worktrees
+            .iter()
+            .map(|wt| wt.path().to_string())
+            .filter(|path| marked.contains(path) && !paths.contains(path))
+            .collect();
+        hidden.sort();
+        paths.extend(hidden);
+        paths
+    }
+
+    /// Unpinned worktrees whose remote branch is gone, among the marked ones or,
+    /// when nothing is marked, among those matching the filter.
+    pub fn prunable_paths(&mut self) -> Vec<String> {
+        let candidates = if self.marked.is_empty() && self.visual_anchor.is_none() {
+            self.filtered_items()
+                .iter()
+                .map(|wt| wt.path().to_string())
+                .collect()
+        } else {
+            self.target_paths()
+        };
+        candidates
+            .into_iter()
+            .filter(|path| {
+                self.worktrees
+                    .iter()
+                    .any(|wt| wt.path() == path && wt.remote_status == RemoteStatus::Gone)
+                    && !self.is_pinned(path)
+            })
+            .collect()
+    }
+
+    /// `repo / branch` of the worktree at `path`, as shown in the list.
+    pub fn label(&self, path: &str) -> String {
+        match self.worktrees.iter().find(|wt| wt.path() == path) {
+            Some(wt) => format!("{} / {}", self.repository_of(path), wt.branch),
+            None => path.to_string(),
+        }
+    }
+
+    /// Marks the worktrees at `paths` as having a live multiplexer session.
+    pub fn set_sessions(&mut self, paths: HashSet<String>) {
+        self.sessions = paths;
+    }
+
+    pub fn worktree_mut(&mut self, path: &str) -> Option<&mut git::Worktree> {
+        self.worktrees.iter_mut().find(|wt| wt.path() == path)
+    }
+
+    fn visual_range_paths(&mut self) -> Vec<String> {
+        let (Some(anchor), Some(current)) = (self.visual_anchor, self.selected_index) else {
+            return Vec::new();
+        };
+        let range = anchor.min(current)..=anchor.max(current);
+        let paths: Vec<String> = self
+            .filtered_items()
+            .iter()
+            .map(|wt| wt.path().to_string())
+            .collect();
+        self.rows()
+            .into_iter()
+            .enumerate()
+            .filter(|(row, _)| range.contains(row))
+            .filter_map(|(_, row)| match row {
+                Row::Worktree(index) => paths.get(index).cloned(),
+                Row::Repository { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Name of the repository whose header is selected in the grouped view.
+    pub fn selected_repository_header(&mut self) -> Option<String> {
+        let index = self.selected_index?;
+        match self.rows().into_iter().nth(index)? {
+            Row::Repository { name, .. } => Some(name),
+            Row::Worktree(_) => None,
+        }
+    }
+
+    fn set_collapsed(&mut self, repo: String, collapsed: bool) {
+        if collapsed {
+            self.collapsed.insert(repo.clone());
+        } else {
+            self.collapsed.remove(&repo);
+        }
+        let index = self
+            .rows()
+            .iter()
+            .position(|row| matches!(row, Row::Repository { name, .. } if *name == repo));
+        if index.is_some() {
+            self.selected_index = index;
+            self.state.select(index);
+        }
+    }
+
+    pub fn repository_of(&self, path: &str) -> String {
+        self.repository_names.get(path).cloned().unwrap_or_default()
+    }
+
+    /// The rows of the list. While filtering, every repository is expanded so
+    /// that all matches show.
+    fn rows(&mut self) -> Vec<Row> {
+        let paths: Vec<String> = self
+            .filtered_items()
+            .iter()
+            .map(|wt| wt.path().to_string())
+            .collect();
+        if !self.grouped {
+            return (0..paths.len()).map(Row::Worktree).collect();
+        }
+        // Repositories are ordered by their first worktree, so the sort mode
+        // and pins also order the groups.
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for (index, path) in paths.iter().enumerate() {
+            let repo = self.repository_of(path);
+            match groups.iter_mut().find(|(name, _)| *name == repo) {
+                Some((_, indexes)) => indexes.push(index),
+                None => groups.push((repo, vec![index])),
+            }
+        }
+        let filtering = !self.filter.value.is_empty();
+        let mut rows = Vec::new();
+        for (name, worktrees) in groups {
+            let collapsed = !filtering && self.collapsed.contains(&name);
+            let worktree_rows: Vec<Row> = if collapsed {
+                Vec::new()
+            } else {
+                worktrees.iter().copied().map(Row::Worktree).collect()
+            };
+            rows.push(Row::Repository {
+                name,
+                collapsed,
+                worktrees,
+            });
+            rows.extend(worktree_rows);
+        }
+        rows
+    }
+
+    /// Selects the first worktree row, skipping the repository header of the
+    /// grouped view.
+    fn select_first_worktree(&mut self) {
+        let index = self
+            .rows()
+            .iter()
+            .position(|row| matches!(row, Row::Worktree(_)));
+        self.selected_index = index.or(Some(0));
+        self.state.select(self.selected_index);
+    }
+
+    /// Selects the worktree at `path`, expanding its repository if needed.
+    /// Returns `false` if it is filtered out.
+    fn select_path(&mut self, path: &str) -> bool {
+        let Some(index) = self
+            .filtered_items()
+            .iter()
+            .position(|wt| wt.path() == path)
+        else {
+            return false;
+        };
+        let repo = self.repository_of(path);
+        self.collapsed.remove(&repo);
+        let row = self
+            .rows()
+            .iter()
+            .position(|row| matches!(row, Row::Worktree(i) if *i == index));
+        self.selected_index = row;
+        self.state.select(row);
+        row.is_some()
+    }
+
+    pub fn set_usage(&mut self, usage: HashMap<String, Usage>) {
+        self.usage = usage;
+    }
+
     /// Clears any active filter, finds the worktree matching the given branch name,
     /// and selects it. Returns `true` if found, `false` otherwise.
     pub fn select_worktree_by_branch(&mut self, branch: &str) -> bool {
-        let exists = self.worktrees.iter().any(|wt| wt.name() == branch);
+        let exists = self.worktrees.iter().any(|wt| wt.branch == branch);
         if !exists {
             return false;
         }
         self.filter.clear();
-        let index = self
-            .filtered_items()
+        let path = self
+            .worktrees
             .iter()
-            .position(|wt| wt.name() == branch);
-        if let Some(idx) = index {
-            self.selected_index = Some(idx);
-            self.state.select(Some(idx));
-            true
-        } else {
-            false
+            .find(|wt| wt.branch == branch)
+            .map(|wt| wt.path().to_string());
+        path.is_some_and(|path| self.select_path(&path))
+    }
+
+    pub fn worktrees(&self) -> &[git::Worktree] {
+        &self.worktrees
+    }
+
+    pub fn set_pr_status(&mut self, worktree_path: String, pr_status: Option<PrStatus>) {
+        match pr_status {
+            Some(status) => self.pr_statuses.insert(worktree_path, status),
+            None => self.pr_statuses.remove(&worktree_path),
+        };
+    }
+
+    pub fn metadata(&self, worktree_path: &str) -> Option<&WorktreeMetadata> {
+        self.metadata.get(worktree_path)
+    }
+
+    pub fn is_pinned(&self, worktree_path: &str) -> bool {
+        self.metadata
+            .get(worktree_path)
+            .is_some_and(|metadata| metadata.pinned)
+    }
+
+    /// Pinning reorders the list, so the selected worktree is selected again
+    /// at its new position.
+    pub fn set_metadata(&mut self, worktree_path: String, metadata: Option<WorktreeMetadata>) {
+        let selected_path = self.selected_worktree_path();
+        match metadata {
+            Some(metadata) => self.metadata.insert(worktree_path, metadata),
+            None => self.metadata.remove(&worktree_path),
+        };
+        if let Some(selected_path) = selected_path {
+            self.select_path(&selected_path);
         }
     }
 
     pub fn add(&mut self, new_worktree: git::Worktree) {
         let new_worktree_path = new_worktree.path().to_string();
+        self.repository_names
+            .insert(new_worktree_path.clone(), repository_name_of(&new_worktree));
         self.worktrees.push(new_worktree);
-        let new_worktree_index = self
-            .filtered_items()
-            .iter()
-            .position(|wt| wt.path().to_string().eq(&new_worktree_path));
-
-        self.state.select(new_worktree_index);
-        self.selected_index = new_worktree_index;
+        if !self.select_path(&new_worktree_path) {
+            self.selected_index = None;
+            self.state.select(None);
+        }
     }
 
-    pub fn delete_selected_worktree(&mut self) -> eyre::Result<()> {
-        if let Some(path) = self.selected_worktree_path() {
-            if let Some(index) = self.worktrees.iter().position(|w| w.path() == path) {
-                let result = git::delete_worktree(&self.worktrees[index]);
-                self.worktrees.remove(index);
-                result?;
-            }
+    /// Deletes the worktree at `path` and drops it from the list, also when
+    /// deletion fails part way.
+    pub fn delete_worktree(&mut self, path: &str) -> eyre::Result<()> {
+        if let Some(index) = self.worktrees.iter().position(|w| w.path() == path) {
+            let result = git::delete_worktree(&self.worktrees[index]);
+            self.worktrees.remove(index);
+            self.marked.remove(path);
+            self.visual_anchor = None;
+            result?;
         }
         Ok(())
     }
 
+    pub fn selected_worktree_mut(&mut self) -> Option<&mut git::Worktree> {
+        let path = self.selected_worktree_path()?;
+        self.worktrees.iter_mut().find(|wt| wt.path() == path)
+    }
+
     pub fn selected_worktree_path(&mut self) -> Option<String> {
-        self.selected_index.and_then(|index| {
-            self.filtered_items()
-                .get(index)
-                .map(|wt| wt.path().to_string())
-        })
+        let index = match self.rows().into_iter().nth(self.selected_index?)? {
+            Row::Worktree(index) => index,
+            Row::Repository { .. } => return None,
+        };
+        self.filtered_items()
+            .get(index)
+            .map(|wt| wt.path().to_string())
     }
 }
 
-fn worktree_to_list_item(
+fn worktree_line(
     remote_status: RemoteStatus,
     is_dirty: bool,
     path: &str,
+    branch: &str,
     worktrees_dir: &str,
-) -> ListItem<'static> {
+    show_repo: bool,
+) -> Line<'static> {
+    let theme = theme::current();
     let (remote_indicator, indicator_color) = match remote_status {
-        RemoteStatus::Exists => ("✔", GREEN.c400),
-        RemoteStatus::Gone => ("✘", RED.c400),
-        RemoteStatus::NeverPushed => ("⬆", AMBER.c400),
+        RemoteStatus::Exists => (theme.glyphs.pushed, theme.accent),
+        RemoteStatus::Gone => (theme.glyphs.gone, theme.danger),
+        RemoteStatus::NeverPushed => (theme.glyphs.never_pushed, theme.warning),
     };
 
     let indicator_span = Span::styled(
@@ -303,89 +809,234 @@ fn worktree_to_list_item(
         .unwrap_or(path)
         .trim_start_matches('/');
 
-    let line = if let Some(sep) = relative.find('/') {
+    // Show the branch rather than the directory, which may have its `/`
+    // replaced.
+    if let Some(sep) = relative.find('/') {
         let repo = &relative[..sep];
-        let branch = relative[sep + 1..].trim_end_matches('/');
-        let repo_span = Span::styled(repo.to_string(), Style::default().fg(SLATE.c400));
-        let sep_span = Span::styled(" / ", Style::default().fg(SLATE.c600));
+        let repo_span = Span::styled(repo.to_string(), Style::default().fg(theme.text_dim));
+        let sep_span = Span::styled(" / ", Style::default().fg(theme.faint));
         let branch_span = Span::styled(
             branch.to_string(),
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         );
+        let mut spans = vec![indicator_span];
+        if show_repo {
+            spans.extend([repo_span, sep_span]);
+        }
+        spans.push(branch_span);
         if is_dirty {
-            let dirty_span = Span::styled(" *", Style::default().fg(AMBER.c400));
-            Line::from(vec![
-                indicator_span,
-                repo_span,
-                sep_span,
-                branch_span,
-                dirty_span,
-            ])
-        } else {
-            Line::from(vec![indicator_span, repo_span, sep_span, branch_span])
+            spans.push(Span::styled(
+                format!(" {}", theme.glyphs.dirty),
+                Style::default().fg(theme.warning),
+            ));
         }
+        Line::from(spans)
     } else {
         let path_span = Span::from(relative.to_string());
         if is_dirty {
-            let dirty_span = Span::styled(" *", Style::default().fg(AMBER.c400));
+            let dirty_span = Span::styled(
+                format!(" {}", theme.glyphs.dirty),
+                Style::default().fg(theme.warning),
+            );
             Line::from(vec![indicator_span, path_span, dirty_span])
         } else {
             Line::from(vec![indicator_span, path_span])
         }
-    };
+    }
+}
+
+/// Header of a repository in the grouped view: its worktree count and how
+/// many of them are in each state.
+fn repository_header(
+    name: &str,
+    collapsed: bool,
+    statuses: &[(RemoteStatus, bool)],
+) -> Line<'static> {
+    let theme = theme::current();
+    let count = |f: fn(&(RemoteStatus, bool)) -> bool| statuses.iter().filter(|s| f(s)).count();
+    let mut spans = vec![
+        Span::styled(
+            format!(
+                "{} ",
+                if collapsed {
+                    theme.glyphs.collapsed
+                } else {
+                    theme.glyphs.expanded
+                }
+            ),
+            Style::default().fg(theme.text_dim),
+        ),
+        Span::styled(
+            name.to_string(),
+            Style::default()
+                .fg(theme.accent_bright)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" ({})", statuses.len()),
+            Style::default().fg(theme.text_dim),
+        ),
+    ];
+    for (glyph, color, n) in [
+        (
+            theme.glyphs.pushed,
+            theme.accent,
+            count(|s| matches!(s.0, RemoteStatus::Exists)),
+        ),
+        (
+            theme.glyphs.gone,
+            theme.danger,
+            count(|s| matches!(s.0, RemoteStatus::Gone)),
+        ),
+        (
+            theme.glyphs.never_pushed,
+            theme.warning,
+            count(|s| matches!(s.0, RemoteStatus::NeverPushed)),
+        ),
+        (theme.glyphs.dirty, theme.warning, count(|s| s.1)),
+    ] {
+        if n > 0 {
+            spans.push(Span::styled(
+                format!("  {}{}", glyph, n),
+                Style::default().fg(color),
+            ));
+        }
+    }
+    Line::from(spans)
+}
+
+/// Repository of a worktree, or an empty name when it cannot be opened.
+fn repository_name_of(worktree: &git::Worktree) -> String {
+    worktree.repository_name().unwrap_or_default()
+}
+
+/// Ticket id and tags shown after the worktree name.
+fn metadata_spans(metadata: &WorktreeMetadata) -> Vec<Span<'static>> {
+    let theme = theme::current();
+    let mut spans = Vec::new();
+    if let Some(ticket_id) = metadata.ticket_id() {
+        spans.push(Span::styled(
+            format!("  {}", ticket_id),
+            Style::default().fg(theme.warning_soft),
+        ));
+    }
+    for tag in &metadata.tags {
+        spans.push(Span::styled(
+            format!(" [{}]", tag),
+            Style::default().fg(theme.special),
+        ));
+    }
+    spans
+}
 
+fn worktree_to_list_item(
+    mut line: Line<'static>,
+    pr_status: Option<&PrStatus>,
+    name_width: usize,
+) -> ListItem<'static> {
+    if let Some(pr) = pr_status {
+        let padding = name_width.saturating_sub(line.width()) + 2;
+        line.spans.push(Span::raw(" ".repeat(padding)));
+        line.spans.extend(pr_status_spans(pr));
+    }
     ListItem::new(line)
 }
 
+fn pr_status_spans(pr: &PrStatus) -> Vec<Span<'static>> {
+    let theme = theme::current();
+    let (state, state_color) = match pr.state {
+        PrState::Open => ("open", theme.accent),
+        PrState::Draft => ("draft", theme.text_dim),
+        PrState::Merged => ("merged", theme.special),
+        PrState::Closed => ("closed", theme.danger),
+    };
+    let (review, review_color) = match pr.review {
+        Some(ReviewDecision::Approved) => ("approved", theme.accent),
+        Some(ReviewDecision::ChangesRequested) => ("changes requested", theme.danger),
+        Some(ReviewDecision::ReviewRequired) => ("review required", theme.warning),
+        None => ("", theme.hint),
+    };
+    let (ci, ci_color) = match pr.ci {
+        Some(CiStatus::Passing) => (format!("{} ci", theme.glyphs.success), theme.accent),
+        Some(CiStatus::Pending) => (format!("{} ci", theme.glyphs.pending), theme.warning),
+        Some(CiStatus::Failing) => (format!("{} ci", theme.glyphs.failure), theme.danger),
+        None => (String::new(), theme.hint),
+    };
+    vec![
+        Span::styled(
+            format!("{:<7}", format!("#{}", pr.number)),
+            Style::default().fg(theme.text_dim),
+        ),
+        Span::styled(format!("{:<8}", state), Style::default().fg(state_color)),
+        Span::styled(format!("{:<19}", review), Style::default().fg(review_color)),
+        Span::styled(ci, Style::default().fg(ci_color)),
+    ]
+}
+
 impl ListComponent<git::Worktree> for WorktreesComponent {
     fn filtered_items(&mut self) -> Vec<&git::Worktree> {
-        let query = self.filter.value.as_str();
-        if query.is_empty() {
-            let mut items: Vec<&git::Worktree> = self.worktrees.iter().collect();
+        let query = Query::parse(&self.filter.value);
+        let now = history::now();
+        let usage = &self.usage;
+        let metadata = &self.metadata;
+        let repository_names = &self.repository_names;
+        let is_pinned = |wt: &git::Worktree| metadata.get(wt.path()).is_some_and(|m| m.pinned);
+        let frecency = |wt: &git::Worktree| usage.get(wt.path()).map_or(0.0, |u| u.frecency(now));
+        let candidates = self.worktrees.iter().filter(|wt| {
+            let facts = WorktreeFacts {
+                repo: repository_names.get(wt.path()).map_or("", |r| r.as_str()),
+                remote_status: wt.remote_status,
+                is_dirty: wt.is_dirty,
+                pinned: is_pinned(wt),
+                tags: metadata.get(wt.path()).map_or(&[], |m| m.tags.as_slice()),
+                last_commit_time: wt.last_commit_time,
+            };
+            query.matches(&facts, now)
+        });
+        if query.words.is_empty() {
+            let mut items: Vec<&git::Worktree> = candidates.collect();
             items.sort_by(|a, b| a.path().cmp(b.path()));
+            // Stable sorts, so ties stay in alphabetical order.
+            match self.sort_mode {
+                SortMode::Alphabetical => {}
+                SortMode::MostRecent => items.sort_by_key(|wt| {
+                    std::cmp::Reverse(usage.get(wt.path()).map_or(0, |u| u.last_used))
+                }),
+                SortMode::Frecency => items.sort_by(|a, b| frecency(b).total_cmp(&frecency(a))),
+                SortMode::LastCommit => {
+                    items.sort_by_key(|wt| std::cmp::Reverse(wt.last_commit_time))
+                }
+            }
+            items.sort_by_key(|wt| !is_pinned(wt));
             return items;
         }
         let worktrees_dir = self.worktrees_dir.as_str();
-        let mut matcher = Matcher::new(Config::DEFAULT);
-        // Pair each word with its per-word minimum score threshold.
-        // Short words (1-2 chars) have low scores due to gap penalties on
-        // longer haystacks, so we accept any match for them.
-        let patterns: Vec<(Pattern, u32)> = query
-            .split_whitespace()
-            .map(|w| {
-                let min = if w.len() >= 3 { 70 } else { 1 };
-                (
-                    Pattern::parse(w, CaseMatching::Ignore, Normalization::Smart),
-                    min,
-                )
-            })
-            .collect();
-        let mut buf = Vec::new();
-        let mut scored: Vec<(&git::Worktree, u32)> = self
-            .worktrees
-            .iter()
+        let mut words = WordMatcher::new(&query.words);
+        let mut scored: Vec<(&git::Worktree, u32)> = candidates
             .filter_map(|wt| {
                 let path = wt.path().trim_end_matches('/');
                 let display = path
                     .strip_prefix(worktrees_dir)
                     .unwrap_or(path)
                     .trim_start_matches('/');
-                let mut total = 0u32;
-                for (pattern, min_score) in &patterns {
-                    match pattern.score(Utf32Str::new(display, &mut buf), &mut matcher) {
-                        Some(s) if s >= *min_score => total += s,
-                        _ => return None,
-                    }
-                }
-                Some((wt, total))
+                Some((wt, words.score(display)?))
             })
             .collect();
-        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        // Equal scores are common for short queries; rank frequently used
+        // worktrees first.
+        scored.sort_by(|(a, a_score), (b, b_score)| {
+            is_pinned(b)
+                .cmp(&is_pinned(a))
+                .then_with(|| b_score.cmp(a_score))
+                .then_with(|| frecency(b).total_cmp(&frecency(a)))
+        });
         scored.into_iter().map(|(wt, _)| wt).collect()
     }
 
+    fn row_count(&mut self) -> usize {
+        self.rows().len()
+    }
+
     fn get_state(&mut self) -> &mut ListState {
         &mut self.state
     }
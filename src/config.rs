@@ -0,0 +1,273 @@
+use color_eyre::eyre::{self, WrapErr};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    dirs,
+    git::UntrackedConfig,
+    hooks::{self, HooksConfig},
+    open::OpenConfig,
+    sessions::SessionsConfig,
+    theme::ThemeConfig,
+    tracker::TrackerConfig,
+};
+
+const CONFIG_FILE: &str = "config.toml";
+
+/// Settings read from `config.toml` in the config directory. The file and
+/// every section in it are optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub tracker: Option<TrackerConfig>,
+    pub branch: BranchConfig,
+    /// Per-repository overrides, keyed by repository name.
+    pub repos: HashMap<String, RepoConfig>,
+    pub theme: ThemeConfig,
+    /// Hooks of every repository, unless overridden.
+    pub hooks: HooksConfig,
+    /// Files brought into new worktrees of every repository, unless
+    /// overridden.
+    pub untracked: UntrackedConfig,
+    pub open: OpenConfig,
+    pub sessions: SessionsConfig,
+}
+
+/// The `[branch]` section: how new branches and their worktree directories
+/// are named.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BranchConfig {
+    /// Templates offered in the create popup, e.g. `{type}/{name}`. Supports
+    /// the `{name}`, `{type}`, `{user}` and `{date}` placeholders.
+    pub templates: Vec<String>,
+    /// Values cycled through for the `{type}` placeholder.
+    pub types: Vec<String>,
+    /// Value of the `{user}` placeholder, `$USER` when unset.
+    pub user: Option<String>,
+    /// Replaces every `/` of the branch name in the worktree directory name,
+    /// so `feat/login` lives in `feat-login`.
+    pub directory_separator: String,
+}
+
+impl Default for BranchConfig {
+    fn default() -> Self {
+        Self {
+            templates: Vec::new(),
+            types: ["feat", "fix", "chore", "docs", "refactor", "test"]
+                .map(String::from)
+                .to_vec(),
+            user: None,
+            directory_separator: "-".to_string(),
+        }
+    }
+}
+
+impl BranchConfig {
+    pub fn user(&self) -> String {
+        self.user
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .unwrap_or_default()
+    }
+
+    /// Name of the worktree directory for `branch`.
+    pub fn directory_name(&self, branch: &str) -> String {
+        branch.replace('/', &self.directory_separator)
+    }
+}
+
+/// A `[repos.<name>]` section.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepoConfig {
+    /// Replaces `branch.templates` for this repository.
+    pub branch_templates: Option<Vec<String>>,
+    /// Replaces the `[hooks]` lists it sets for this repository.
+    pub hooks: HooksConfig,
+    /// Runs the hooks of the repository's own `.shanti.toml`. Off by default,
+    /// as they come with the repository rather than from you.
+    pub trust_hooks: bool,
+    /// Replaces the `[untracked]` lists it sets for this repository.
+    pub untracked: UntrackedConfig,
+}
+
+impl Config {
+    pub fn load() -> eyre::Result<Self> {
+        let path = config_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Could not read config file {:?}", path))?;
+        Self::parse(&text).wrap_err_with(|| format!("Could not parse config file {:?}", path))
+    }
+
+    pub fn parse(text: &str) -> eyre::Result<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Branch name templates offered when creating a worktree in `repo`.
+    pub fn branch_templates(&self, repo: &str) -> Vec<String> {
+        self.repos
+            .get(repo)
+            .and_then(|repo| repo.branch_templates.clone())
+            .unwrap_or_else(|| self.branch.templates.clone())
+    }
+
+    /// Hooks of `repo`: its `[repos.<name>.hooks]`, then the `.shanti.toml` in
+    /// `repo_path` if the repository is trusted, then `[hooks]`.
+    pub fn hooks(&self, repo: &str, repo_path: &Path) -> eyre::Result<HooksConfig> {
+        let repo_config = self.repos.get(repo);
+        let mut hooks = repo_config.map(|r| r.hooks.clone()).unwrap_or_default();
+        if self.trusts_repo_hooks(repo) {
+            hooks = hooks.or(hooks::load_repo_hooks(repo_path)?);
+        }
+        Ok(hooks.or(self.hooks.clone()))
+    }
+
+    pub fn trusts_repo_hooks(&self, repo: &str) -> bool {
+        self.repos.get(repo).is_some_and(|r| r.trust_hooks)
+    }
+
+    /// Files brought into new worktrees of `repo`.
+    pub fn untracked(&self, repo: &str) -> UntrackedConfig {
+        let repo_config = self.repos.get(repo);
+        repo_config
+            .map(|r| r.untracked.clone())
+            .unwrap_or_default()
+            .or(self.untracked.clone())
+    }
+}
+
+/// Values of the placeholders in a branch name template.
+pub struct TemplateValues<'a> {
+    pub name: &'a str,
+    pub kind: &'a str,
+    pub user: &'a str,
+    pub date: &'a str,
+}
+
+/// Fills the `{name}`, `{type}`, `{user}` and `{date}` placeholders of
+/// `template`.
+pub fn expand_branch_template(template: &str, values: &TemplateValues) -> String {
+    template
+        .replace("{name}", values.name)
+        .replace("{type}", values.kind)
+        .replace("{user}", values.user)
+        .replace("{date}", values.date)
+}
+
+pub fn config_path() -> eyre::Result<PathBuf> {
+    Ok(dirs::get_config_dir()?.join(CONFIG_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracker::TrackerKind;
+
+    #[test]
+    fn test_parse_tracker() {
+        let config = Config::parse(
+            r#"
+            [tracker]
+            kind = "jira"
+            url = "https://acme.atlassian.net"
+            "#,
+        )
+        .unwrap();
+        let tracker = config.tracker.unwrap();
+        assert_eq!(tracker.kind, TrackerKind::Jira);
+        assert_eq!(tracker.branch_template, "{key}-{slug}");
+    }
+
+    #[test]
+    fn test_parse_empty_and_unknown_keys() {
+        assert!(Config::parse("").unwrap().tracker.is_none());
+        assert!(Config::parse("[trackr]\nkind = \"jira\"").is_err());
+    }
+
+    #[test]
+    fn test_branch_templates_per_repo() {
+        let config = Config::parse(
+            r#"
+            [branch]
+            templates = ["{type}/{name}"]
+            directory_separator = "_"
+
+            [repos.shanti]
+            branch_templates = ["{user}/{name}", "{user}/{date}-{name}"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.branch_templates("other"), ["{type}/{name}"]);
+        assert_eq!(config.branch_templates("shanti").len(), 2);
+        assert_eq!(config.branch.directory_name("feat/login"), "feat_login");
+    }
+
+    #[test]
+    fn test_hooks_per_repo() {
+        let repo_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            repo_dir.path().join(hooks::REPO_FILE),
+            "[hooks]\npost_create = [\"npm ci\"]\n",
+        )
+        .unwrap();
+        let config = Config::parse(
+            r#"
+            [hooks]
+            post_create = ["direnv allow"]
+
+            [repos.web]
+            trust_hooks = true
+
+            [repos.api.hooks]
+            post_create = []
+            "#,
+        )
+        .unwrap();
+        let post_create = |repo| config.hooks(repo, repo_dir.path()).unwrap().post_create;
+        assert_eq!(post_create("web"), Some(vec!["npm ci".to_string()]));
+        assert_eq!(post_create("other"), Some(vec!["direnv allow".to_string()]));
+        assert_eq!(post_create("api"), Some(vec![]));
+    }
+
+    #[test]
+    fn test_untracked_per_repo() {
+        let config = Config::parse(
+            r#"
+            [untracked]
+            copy = [".env"]
+            symlink = ["node_modules"]
+
+            [repos.api.untracked]
+            copy = [".env", "certs/*.pem"]
+            "#,
+        )
+        .unwrap();
+        let api = config.untracked("api");
+        assert_eq!(api.copy.unwrap().len(), 2);
+        assert_eq!(api.symlink, Some(vec!["node_modules".to_string()]));
+        assert_eq!(config.untracked("web").copy, Some(vec![".env".to_string()]));
+    }
+
+    #[test]
+    fn test_expand_branch_template() {
+        let values = TemplateValues {
+            name: "login",
+            kind: "fix",
+            user: "ada",
+            date: "2024-05-01",
+        };
+        assert_eq!(
+            expand_branch_template("{user}/{type}/{date}-{name}", &values),
+            "ada/fix/2024-05-01-login"
+        );
+        assert_eq!(expand_branch_template("{name}", &values), "login");
+    }
+}
@@ -1,6 +1,6 @@
 use color_eyre::eyre::{self};
 use directories::ProjectDirs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 pub fn get_data_dir() -> eyre::Result<PathBuf> {
     let directory = if let Ok(s) = std::env::var("SHANTI_DATA") {
         PathBuf::from(s)
@@ -11,13 +11,21 @@ pub fn get_data_dir() -> eyre::Result<PathBuf> {
     };
     Ok(directory)
 }
-// pub fn get_config_dir() -> eyre::Result<PathBuf> {
-//     let directory = if let Ok(s) = std::env::var("SHANTI_CONFIG") {
-//         PathBuf::from(s)
-//     } else if let Some(proj_dirs) = ProjectDirs::from("com", "muzomer", "shanti") {
-//         proj_dirs.config_local_dir().to_path_buf()
-//     } else {
-//         return Err(eyre::eyre!("Unable to find config directory for shanti"));
-//     };
-//     Ok(directory)
-// }
+pub fn get_config_dir() -> eyre::Result<PathBuf> {
+    let directory = if let Ok(s) = std::env::var("SHANTI_CONFIG") {
+        PathBuf::from(s)
+    } else if let Some(proj_dirs) = ProjectDirs::from("com", "muzomer", "shanti") {
+        proj_dirs.config_local_dir().to_path_buf()
+    } else {
+        return Err(eyre::eyre!("Unable to find config directory for shanti"));
+    };
+    Ok(directory)
+}
+
+/// The file `name` in `data_dir`, which is `None` when `get_data_dir` found
+/// no data directory; see `Args::data_dir`.
+pub fn data_file(data_dir: Option<&Path>, name: &str) -> eyre::Result<PathBuf> {
+    data_dir
+        .map(|dir| dir.join(name))
+        .ok_or_else(|| eyre::eyre!("Unable to find data directory for shanti"))
+}
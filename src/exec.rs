@@ -0,0 +1,240 @@
+use color_eyre::eyre;
+use std::{
+    collections::VecDeque,
+    io::Write,
+    sync::{
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use crate::{
+    cli::Args,
+    components::{Query, WordMatcher, WorktreeFacts},
+    git::{self, RemoteStatus},
+    history,
+    hooks::{self, HookContext},
+    metadata::MetadataStore,
+};
+
+/// Which worktrees `shanti exec` runs the command in; unset parts match all.
+#[derive(Debug, Default)]
+pub struct Selection {
+    pub repo: Option<String>,
+    /// A filter as typed above the worktrees list, e.g. `login is:dirty`.
+    pub filter: Option<String>,
+    pub status: Option<RemoteStatus>,
+}
+
+pub enum ExecEvent {
+    /// A line the command printed in the worktree with the label.
+    Output(String, String),
+    /// The command finished in the worktree with the label, with the error
+    /// when it failed.
+    Finished(String, Result<(), String>),
+}
+
+/// `repo/branch`, which the output of a worktree is prefixed with.
+pub fn label(context: &HookContext) -> String {
+    format!("{}/{}", context.repo, context.branch)
+}
+
+/// The `sh -c` command line of the words after `--`. A single word is used
+/// as it is, so `'make lint | tail'` can use pipes; several are quoted.
+pub fn command_line(words: &[String]) -> String {
+    match words {
+        [command] => command.clone(),
+        _ => words
+            .iter()
+            .map(|word| quote(word))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+fn quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// The worktrees of the repositories in `--repos-dir` that `selection` picks,
+/// in the order of their labels. Commands get the same `SHANTI_*`
+/// environment as hooks, with `SHANTI_HOOK=exec`.
+pub fn targets(args: &Args, selection: &Selection) -> Vec<HookContext> {
+    let repositories: Vec<_> = args
+        .repos_dirs
+        .iter()
+        .flat_map(|dir| git::list_repositories(dir, args.run_fetch))
+        .collect();
+    let metadata = MetadataStore::load(args.data_dir.as_deref());
+    let query = Query::parse(selection.filter.as_deref().unwrap_or_default());
+    let mut words = WordMatcher::new(&query.words);
+    let now = history::now();
+    let mut targets: Vec<HookContext> = git::worktrees_of_repositories(&repositories)
+        .iter()
+        .filter_map(|wt| Some((wt, HookContext::for_worktree(wt, "exec")?)))
+        .filter(|(wt, context)| {
+            let metadata = metadata.get(&context.repo, wt.name());
+            let facts = WorktreeFacts {
+                repo: &context.repo,
+                remote_status: wt.remote_status,
+                is_dirty: wt.is_dirty,
+                pinned: metadata.is_some_and(|m| m.pinned),
+                tags: metadata.map_or(&[], |m| m.tags.as_slice()),
+                last_commit_time: wt.last_commit_time,
+            };
+            selection
+                .repo
+                .as_ref()
+                .is_none_or(|repo| *repo == context.repo)
+                && selection
+                    .status
+                    .is_none_or(|status| status == wt.remote_status)
+                && query.matches(&facts, now)
+                && words.score(&label(context)).is_some()
+        })
+        .map(|(_, context)| context)
+        .collect();
+    targets.sort_by_key(label);
+    targets
+}
+
+/// Runs `command` with `sh -c` in each of `targets`, `parallel` of them at a
+/// time, streaming the output over the returned channel. The channel closes
+/// once the command finished everywhere.
+pub fn run_in_background(
+    command: String,
+    targets: Vec<HookContext>,
+    parallel: usize,
+) -> Receiver<ExecEvent> {
+    let (sender, receiver) = mpsc::channel();
+    let queue = Arc::new(Mutex::new(VecDeque::from(targets)));
+    for _ in 0..parallel.max(1) {
+        let (queue, sender, command) = (queue.clone(), sender.clone(), command.clone());
+        thread::spawn(move || {
+            // Popped in a closure, so the lock isn't held while the command runs.
+            let next = || queue.lock().ok().and_then(|mut queue| queue.pop_front());
+            while let Some(context) = next() {
+                let label = label(&context);
+                let (output, output_label) = (sender.clone(), label.clone());
+                let result = hooks::run_command(&command, &context, move |line| {
+                    let _ = output.send(ExecEvent::Output(output_label.clone(), line));
+                })
+                .map_err(|e| format!("{:#}", e));
+                let _ = sender.send(ExecEvent::Finished(label, result));
+            }
+        });
+    }
+    receiver
+}
+
+/// Runs `command` in `targets` for `shanti exec`: prints its output with each
+/// line prefixed by the worktree, then whether it passed in each. Returns
+/// whether it passed everywhere.
+pub fn run(command: &str, targets: Vec<HookContext>, parallel: usize) -> eyre::Result<bool> {
+    if targets.is_empty() {
+        eprintln!("No worktree matches");
+        return Ok(true);
+    }
+    let width = targets
+        .iter()
+        .map(|target| label(target).chars().count())
+        .max()
+        .unwrap_or_default();
+    let events = run_in_background(command.to_string(), targets, parallel);
+    let mut stdout = std::io::stdout().lock();
+    let mut results = vec![];
+    for event in events {
+        match event {
+            ExecEvent::Output(label, line) => writeln!(stdout, "{:<width$} | {}", label, line)?,
+            ExecEvent::Finished(label, result) => results.push((label, result)),
+        }
+    }
+
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    writeln!(stdout)?;
+    for (label, result) in &results {
+        match result {
+            Ok(()) => writeln!(stdout, "passed  {}", label)?,
+            Err(error) => writeln!(stdout, "failed  {}: {}", label, error)?,
+        }
+    }
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    writeln!(
+        stdout,
+        "{} passed, {} failed",
+        results.len() - failed,
+        failed
+    )?;
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_line() {
+        let words = |words: &[&str]| words.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            command_line(&words(&["make lint | tail -n 5"])),
+            "make lint | tail -n 5"
+        );
+        assert_eq!(
+            command_line(&words(&["git", "pull", "--ff-only"])),
+            "git pull --ff-only"
+        );
+        assert_eq!(
+            command_line(&words(&["git", "commit", "-m", "it's done", ""])),
+            r"git commit -m 'it'\''s done' ''"
+        );
+    }
+
+    #[test]
+    fn test_run_in_background() {
+        let dirs: Vec<_> = (0..3).map(|_| tempfile::tempdir().unwrap()).collect();
+        let targets = dirs
+            .iter()
+            .enumerate()
+            .map(|(i, dir)| HookContext {
+                hook: "exec",
+                repo: "api".to_string(),
+                repo_path: "/repos/api".to_string(),
+                worktree_path: dir.path().to_str().unwrap().to_string(),
+                branch: format!("feat-{}", i),
+            })
+            .collect();
+        let events = run_in_background(
+            r#"echo "$SHANTI_BRANCH"; test "$SHANTI_BRANCH" != feat-1"#.to_string(),
+            targets,
+            2,
+        );
+        let mut output = vec![];
+        let mut failed = vec![];
+        for event in events {
+            match event {
+                ExecEvent::Output(label, line) => output.push(format!("{} {}", label, line)),
+                ExecEvent::Finished(label, Err(_)) => failed.push(label),
+                ExecEvent::Finished(_, Ok(())) => {}
+            }
+        }
+        output.sort();
+        assert_eq!(
+            output,
+            [
+                "api/feat-0 feat-0",
+                "api/feat-1 feat-1",
+                "api/feat-2 feat-2"
+            ]
+        );
+        assert_eq!(failed, ["api/feat-1"]);
+    }
+}
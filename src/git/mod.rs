@@ -1,5 +1,9 @@
 mod repository;
+mod untracked;
 mod worktree;
 
-pub use repository::{list_repositories, worktrees_of_repositories, Repository};
-pub use worktree::{delete_worktree, RemoteStatus, Worktree};
+pub use repository::{
+    clone_repository, list_repositories, worktrees_of_repositories, CloneProgress, Repository,
+};
+pub use untracked::{Transfer, UntrackedConfig, UntrackedFile};
+pub use worktree::{delete_worktree, fetch_worktree, pull_worktree, RemoteStatus, Worktree};
@@ -3,15 +3,24 @@ use color_eyre::eyre::WrapErr;
 use git2::{Cred, RemoteCallbacks};
 use rayon::prelude::*;
 use std::{
+    cell::Cell,
     ffi::OsStr,
     fs::{self, read_dir},
+    io::Read,
     path::{Path, PathBuf},
+    process::{Command, Stdio},
 };
 use tracing::{debug, error};
 
-use super::RemoteStatus;
+use super::{
+    untracked::{self, UntrackedConfig, UntrackedFile},
+    RemoteStatus,
+};
 
-fn remote_status_of_branch(repo: &git2::Repository, branch: &git2::Branch) -> RemoteStatus {
+pub(super) fn remote_status_of_branch(
+    repo: &git2::Repository,
+    branch: &git2::Branch,
+) -> RemoteStatus {
     let refname = match branch.get().name() {
         Some(n) => n,
         None => return RemoteStatus::NeverPushed,
@@ -28,7 +37,7 @@ fn remote_status_of_branch(repo: &git2::Repository, branch: &git2::Branch) -> Re
     }
 }
 
-fn is_worktree_dirty(worktree_path: &str) -> bool {
+pub(super) fn is_worktree_dirty(worktree_path: &str) -> bool {
     let path = Path::new(worktree_path);
     if path.join(".jj").exists() {
         return false;
@@ -68,13 +77,21 @@ impl Repository {
         }
         Ok(Self(repo))
     }
+    /// Creates a worktree for `branch_name` and brings `untracked` files of the
+    /// main checkout into it. Files that could not be copied or linked don't
+    /// fail the creation; their errors are returned with the worktree.
     pub fn create_new_worktree(
         &self,
-        worktree_name: &str,
+        branch_name: &str,
+        directory_name: &str,
         worktrees_dir: &str,
-    ) -> eyre::Result<super::Worktree> {
+        untracked: &[UntrackedFile],
+    ) -> eyre::Result<(super::Worktree, Vec<String>)> {
         let repo_worktrees_dir = PathBuf::from(worktrees_dir).join(self.name());
-        let new_worktree_dir = PathBuf::from(&repo_worktrees_dir).join(worktree_name);
+        let new_worktree_dir = PathBuf::from(&repo_worktrees_dir).join(directory_name);
+        // git keeps worktree admin files in `.git/worktrees/<name>`, which
+        // cannot be nested.
+        let worktree_name = directory_name.replace('/', "-");
 
         fs::create_dir_all(&repo_worktrees_dir).wrap_err_with(|| {
             format!(
@@ -85,7 +102,7 @@ impl Repository {
 
         // If a remote branch with the same name exists, base the new worktree on it.
         // Otherwise fall back to the repository's default branch, then HEAD.
-        let remote_branch_name = format!("origin/{}", worktree_name);
+        let remote_branch_name = format!("origin/{}", branch_name);
         let local_branch = if let Ok(remote_branch) = self
             .0
             .find_branch(&remote_branch_name, git2::BranchType::Remote)
@@ -93,38 +110,54 @@ impl Repository {
             let commit = remote_branch.get().peel_to_commit().wrap_err_with(|| {
                 format!("Could not resolve remote branch '{}'", remote_branch_name)
             })?;
-            let branch = match self.0.find_branch(worktree_name, git2::BranchType::Local) {
+            let branch = match self.0.find_branch(branch_name, git2::BranchType::Local) {
                 Ok(existing) => existing,
                 Err(_) => {
-                    let mut new_branch = self
-                        .0
-                        .branch(worktree_name, &commit, false)
-                        .wrap_err_with(|| {
-                            format!(
-                                "Could not create local branch '{}' from remote",
-                                worktree_name
-                            )
-                        })?;
+                    let mut new_branch =
+                        self.0
+                            .branch(branch_name, &commit, false)
+                            .wrap_err_with(|| {
+                                format!(
+                                    "Could not create local branch '{}' from remote",
+                                    branch_name
+                                )
+                            })?;
                     new_branch
                         .set_upstream(Some(&remote_branch_name))
                         .wrap_err_with(|| {
-                            format!("Could not set upstream for branch '{}'", worktree_name)
+                            format!("Could not set upstream for branch '{}'", branch_name)
                         })?;
                     new_branch
                 }
             };
             Some(branch)
+        } else if let Ok(existing) = self.0.find_branch(branch_name, git2::BranchType::Local) {
+            Some(existing)
         } else {
-            // No matching remote branch — base on the default branch if available
-            self.find_default_branch_name().and_then(|default_name| {
+            // No matching remote branch — base on the default branch if available.
+            // The branch is always created here, as git would otherwise name it
+            // after the worktree.
+            let default_commit = self.find_default_branch_name().and_then(|default_name| {
                 let remote_name = format!("origin/{}", default_name);
                 let default_branch = self
                     .0
                     .find_branch(&remote_name, git2::BranchType::Remote)
                     .ok()?;
-                let commit = default_branch.get().peel_to_commit().ok()?;
-                self.0.branch(worktree_name, &commit, false).ok()
-            })
+                default_branch.get().peel_to_commit().ok()
+            });
+            let commit = match default_commit {
+                Some(commit) => commit,
+                None => self
+                    .0
+                    .head()
+                    .and_then(|head| head.peel_to_commit())
+                    .wrap_err("Could not resolve HEAD to base the new branch on")?,
+            };
+            let branch = self
+                .0
+                .branch(branch_name, &commit, false)
+                .wrap_err_with(|| format!("Could not create branch '{}'", branch_name))?;
+            Some(branch)
         };
 
         let mut create_worktree_options = git2::WorktreeAddOptions::new();
@@ -136,7 +169,7 @@ impl Repository {
         let created_worktree = self
             .0
             .worktree(
-                worktree_name,
+                &worktree_name,
                 new_worktree_dir.as_path(),
                 Some(&create_worktree_options),
             )
@@ -144,49 +177,47 @@ impl Repository {
 
         let branch = self
             .0
-            .find_branch(worktree_name, git2::BranchType::Local)
+            .find_branch(branch_name, git2::BranchType::Local)
             .wrap_err_with(|| {
                 format!(
                     "Could not find branch '{}' after creating worktree",
-                    worktree_name
+                    branch_name
                 )
             })?;
 
+        let untracked_errors = match self.0.workdir() {
+            Some(workdir) => untracked::bring_into(workdir, &new_worktree_dir, untracked),
+            None => Vec::new(),
+        };
+
         let remote_status = remote_status_of_branch(&self.0, &branch);
-        Ok(super::Worktree {
+        let last_commit_time = super::Worktree::head_commit_time(&created_worktree);
+        let worktree = super::Worktree {
             git_worktree: created_worktree,
+            branch: branch_name.to_string(),
+            last_commit_time,
             remote_status,
             is_dirty: false,
-        })
+        };
+        Ok((worktree, untracked_errors))
     }
 
-    /// Returns the short name of the default remote branch (e.g. "main"), by checking
-    /// `refs/remotes/origin/HEAD` first, then falling back to common names.
-    fn find_default_branch_name(&self) -> Option<String> {
-        if let Ok(head_ref) = self.0.find_reference("refs/remotes/origin/HEAD") {
-            if let Ok(resolved) = head_ref.resolve() {
-                if let Some(name) = resolved.shorthand() {
-                    let short = name.strip_prefix("origin/").unwrap_or(name).to_string();
-                    return Some(short);
-                }
-            }
-        }
-        for default in &["main", "master"] {
-            let remote_name = format!("origin/{}", default);
-            if self
-                .0
-                .find_branch(&remote_name, git2::BranchType::Remote)
-                .is_ok()
-            {
-                return Some(default.to_string());
-            }
+    /// Files of the main checkout that `config` brings into new worktrees.
+    pub fn untracked_files(&self, config: &UntrackedConfig) -> eyre::Result<Vec<UntrackedFile>> {
+        match self.0.workdir() {
+            Some(workdir) => untracked::find(workdir, config),
+            None => Ok(Vec::new()),
         }
-        None
+    }
+
+    /// Returns the short name of the default remote branch (e.g. "main").
+    fn find_default_branch_name(&self) -> Option<String> {
+        default_branch_name(&self.0)
     }
 
     /// Returns a human-readable description of which branch a new worktree would be based on.
-    pub fn resolve_base_branch(&self, worktree_name: &str) -> String {
-        let remote_branch_name = format!("origin/{}", worktree_name);
+    pub fn resolve_base_branch(&self, branch_name: &str) -> String {
+        let remote_branch_name = format!("origin/{}", branch_name);
         if self
             .0
             .find_branch(&remote_branch_name, git2::BranchType::Remote)
@@ -216,7 +247,9 @@ impl Repository {
                 worktrees_arr.iter().for_each(|worktree| {
                     if let Some(worktree_name) = worktree {
                         if let Ok(git_worktree) = self.0.find_worktree(worktree_name) {
-                            let branch = self.0.find_branch(worktree_name, git2::BranchType::Local);
+                            let branch_name = super::Worktree::head_branch(&git_worktree)
+                                .unwrap_or_else(|| worktree_name.to_string());
+                            let branch = self.0.find_branch(&branch_name, git2::BranchType::Local);
 
                             let remote_status = match branch {
                                 Ok(ref b) => remote_status_of_branch(&self.0, b),
@@ -227,8 +260,11 @@ impl Repository {
                                 git_worktree.path().to_str().unwrap_or("").to_string();
                             let is_dirty = is_worktree_dirty(&worktree_path);
 
+                            let last_commit_time = super::Worktree::head_commit_time(&git_worktree);
                             git_worktrees.push(super::Worktree {
                                 git_worktree,
+                                branch: branch_name,
+                                last_commit_time,
                                 remote_status,
                                 is_dirty,
                             });
@@ -244,16 +280,222 @@ impl Repository {
     }
 }
 
-fn fetch_with_prune(git_repo: &git2::Repository, remote_name: &str) -> Result<(), git2::Error> {
-    let refspecs: Vec<String> = vec![];
-    let mut fetch_opts = git2::FetchOptions::new();
+/// Returns the short name of the default remote branch (e.g. "main"), by checking
+/// `refs/remotes/origin/HEAD` first, then falling back to common names.
+pub(super) fn default_branch_name(repo: &git2::Repository) -> Option<String> {
+    if let Ok(head_ref) = repo.find_reference("refs/remotes/origin/HEAD") {
+        if let Ok(resolved) = head_ref.resolve() {
+            if let Some(name) = resolved.shorthand() {
+                let short = name.strip_prefix("origin/").unwrap_or(name).to_string();
+                return Some(short);
+            }
+        }
+    }
+    for default in &["main", "master"] {
+        let remote_name = format!("origin/{}", default);
+        if repo
+            .find_branch(&remote_name, git2::BranchType::Remote)
+            .is_ok()
+        {
+            return Some(default.to_string());
+        }
+    }
+    None
+}
 
+/// Callbacks shared by every operation that talks to a remote. SSH remotes
+/// authenticate through the SSH agent; HTTPS remotes use `GITHUB_TOKEN` for
+/// GitHub, then the git credential helper.
+pub(super) fn remote_callbacks<'a>() -> RemoteCallbacks<'a> {
     let mut callbacks = RemoteCallbacks::new();
-    callbacks.credentials(|_url, username_from_url, _allowed_types| {
-        Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"))
+    // libgit2 keeps asking for credentials while they are rejected, so give up
+    // after a few attempts instead of looping forever.
+    let attempts = Cell::new(0);
+    callbacks.credentials(move |url, username_from_url, allowed_types| {
+        attempts.set(attempts.get() + 1);
+        if attempts.get() > 3 {
+            return Err(git2::Error::from_str("Authentication failed"));
+        }
+        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+            return Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"));
+        }
+        if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            if url.starts_with("https://github.com/") {
+                if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+                    return Cred::userpass_plaintext("x-access-token", &token);
+                }
+            }
+            let config = git2::Config::open_default()?;
+            return Cred::credential_helper(&config, url, username_from_url);
+        }
+        Cred::default()
+    });
+    callbacks
+}
+
+/// Progress of a running clone, e.g. `Receiving objects` 120 of 1000.
+pub struct CloneProgress {
+    pub stage: String,
+    pub current: usize,
+    pub total: usize,
+}
+
+/// Clones `url` into `dest`. Partial clones (`--filter=blob:none`) are not
+/// supported by libgit2, so they run through the `git` binary instead.
+pub fn clone_repository(
+    url: &str,
+    dest: &Path,
+    partial: bool,
+    on_progress: &dyn Fn(CloneProgress),
+) -> eyre::Result<()> {
+    if partial {
+        return clone_partial_with_git(url, dest, on_progress);
+    }
+
+    let mut callbacks = remote_callbacks();
+    callbacks.transfer_progress(|stats| {
+        let progress = if stats.received_objects() < stats.total_objects() {
+            CloneProgress {
+                stage: "Receiving objects".to_string(),
+                current: stats.received_objects(),
+                total: stats.total_objects(),
+            }
+        } else {
+            CloneProgress {
+                stage: "Resolving deltas".to_string(),
+                current: stats.indexed_deltas(),
+                total: stats.total_deltas(),
+            }
+        };
+        on_progress(progress);
+        true
     });
+    let mut fetch_options = git2::FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.progress(|_path, current, total| {
+        on_progress(CloneProgress {
+            stage: "Checking out files".to_string(),
+            current,
+            total,
+        })
+    });
+
+    git2::build::RepoBuilder::new()
+        .fetch_options(fetch_options)
+        .with_checkout(checkout)
+        .clone(url, dest)
+        .wrap_err_with(|| format!("Could not clone {}", url))?;
+    Ok(())
+}
+
+fn clone_partial_with_git(
+    url: &str,
+    dest: &Path,
+    on_progress: &dyn Fn(CloneProgress),
+) -> eyre::Result<()> {
+    let git_found = Command::new("git")
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    if !git_found {
+        eyre::bail!("Partial clones need the git binary in PATH, which was not found");
+    }
+
+    let mut child = partial_clone_command(url, dest)
+        .spawn()
+        .wrap_err("Failed to run git clone")?;
+
+    // git redraws its progress with \r, so split on both \r and \n.
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let mut output = Vec::new();
+    let mut line = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = stderr
+            .read(&mut buf)
+            .wrap_err("Failed to read git clone output")?;
+        if n == 0 {
+            break;
+        }
+        for &byte in &buf[..n] {
+            if byte == b'\r' || byte == b'\n' {
+                if let Some(progress) = parse_git_progress(&String::from_utf8_lossy(&line)) {
+                    on_progress(progress);
+                } else if !line.is_empty() {
+                    output.extend_from_slice(&line);
+                    output.push(b'\n');
+                }
+                line.clear();
+            } else {
+                line.push(byte);
+            }
+        }
+    }
+    output.extend_from_slice(&line);
+
+    let status = child.wait().wrap_err("Failed to run git clone")?;
+    if !status.success() {
+        let output = String::from_utf8_lossy(&output);
+        eyre::bail!("git clone failed: {}", output.trim());
+    }
+    Ok(())
+}
+
+/// `git clone --filter=blob:none`, set up to never wait for input: the TUI
+/// owns the terminal, so a prompt for a password or host key would hang the
+/// clone. `GITHUB_TOKEN` is passed to GitHub through a credential helper that
+/// reads it from the environment, after the configured helpers, like
+/// `remote_callbacks` does for libgit2; ssh uses the agent.
+fn partial_clone_command(url: &str, dest: &Path) -> Command {
+    let mut command = Command::new("git");
+    if std::env::var_os("GITHUB_TOKEN").is_some() {
+        command.args([
+            "-c",
+            "credential.https://github.com.helper=\
+             !f() { echo username=x-access-token; echo \"password=$GITHUB_TOKEN\"; }; f",
+        ]);
+    }
+    command
+        .args(["clone", "--progress", "--filter=blob:none", url])
+        .arg(dest)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    if std::env::var_os("GIT_SSH_COMMAND").is_none() {
+        command.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes");
+    }
+    command
+}
+
+/// Parses a progress line printed by `git clone --progress`, e.g.
+/// `Receiving objects:  12% (120/1000), 1.00 MiB | 2.00 MiB/s`.
+fn parse_git_progress(line: &str) -> Option<CloneProgress> {
+    let line = line.trim().strip_prefix("remote: ").unwrap_or(line.trim());
+    let (stage, rest) = line.split_once(':')?;
+    let counts = rest.split_once('(')?.1.split_once(')')?.0;
+    let (current, total) = counts.split_once('/')?;
+    Some(CloneProgress {
+        stage: stage.to_string(),
+        current: current.parse().ok()?,
+        total: total.parse().ok()?,
+    })
+}
+
+pub(super) fn fetch_with_prune(
+    git_repo: &git2::Repository,
+    remote_name: &str,
+) -> Result<(), git2::Error> {
+    let refspecs: Vec<String> = vec![];
+    let mut fetch_opts = git2::FetchOptions::new();
+
     fetch_opts.prune(git2::FetchPrune::On);
-    fetch_opts.remote_callbacks(callbacks);
+    fetch_opts.remote_callbacks(remote_callbacks());
     git_repo
         .find_remote(remote_name)?
         .fetch(&refspecs, Some(&mut fetch_opts), None)?;
@@ -373,6 +615,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_git_progress() {
+        let progress =
+            parse_git_progress("Receiving objects:  12% (120/1000), 1.00 MiB | 2.00 MiB/s")
+                .expect("Expected the progress line to be parsed");
+        assert_eq!(progress.stage, "Receiving objects");
+        assert_eq!(progress.current, 120);
+        assert_eq!(progress.total, 1000);
+
+        let progress = parse_git_progress("remote: Counting objects: 100% (5/5), done.")
+            .expect("Expected the remote progress line to be parsed");
+        assert_eq!(progress.stage, "Counting objects");
+
+        assert!(parse_git_progress("Cloning into 'repo'...").is_none());
+    }
+
+    #[test]
+    fn test_partial_clone_never_prompts() {
+        let command = partial_clone_command("https://github.com/acme/api.git", Path::new("api"));
+        let envs: Vec<_> = command.get_envs().collect();
+        assert!(envs.contains(&(
+            std::ffi::OsStr::new("GIT_TERMINAL_PROMPT"),
+            Some(std::ffi::OsStr::new("0"))
+        )));
+        let args: Vec<_> = command.get_args().collect();
+        assert!(args.contains(&std::ffi::OsStr::new("--filter=blob:none")));
+    }
+
+    #[test]
+    fn test_create_worktree_with_slash_in_branch() {
+        let repo_dir = tempdir().expect("Could not create temporary directory");
+        let worktrees_dir = tempdir().expect("Could not create temporary directory");
+        let git_repo = git2::Repository::init(repo_dir.path()).unwrap();
+        {
+            let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+            let tree_id = git_repo.index().unwrap().write_tree().unwrap();
+            let tree = git_repo.find_tree(tree_id).unwrap();
+            git_repo
+                .commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+                .unwrap();
+        }
+        let repo = Repository(git_repo);
+
+        let (worktree, _) = repo
+            .create_new_worktree(
+                "feat/login",
+                "feat-login",
+                worktrees_dir.path().to_str().unwrap(),
+                &[],
+            )
+            .expect("Expected the worktree to be created");
+
+        assert_eq!(worktree.branch, "feat/login");
+        assert!(worktree
+            .path()
+            .trim_end_matches('/')
+            .ends_with(&format!("{}/feat-login", repo.name())));
+        let listed = repo.worktrees();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].branch, "feat/login");
+    }
+
     #[test]
     fn test_list() {
         let temp_dir = tempdir().expect("Could not create temporary directory");
@@ -0,0 +1,199 @@
+use color_eyre::eyre::{self, eyre, WrapErr};
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+/// An `[untracked]` section, in `config.toml` or under `[repos.<name>]`: files
+/// of the main checkout that git doesn't carry over, such as `.env` or
+/// `node_modules`, to bring into new worktrees. Patterns are globs relative to
+/// the repository's working directory; unset lists fall back to `[untracked]`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UntrackedConfig {
+    /// Copied into the worktree, e.g. `.env` or `certs/*.pem`.
+    pub copy: Option<Vec<String>>,
+    /// Linked from the worktree, e.g. large caches such as `node_modules`.
+    pub symlink: Option<Vec<String>>,
+}
+
+impl UntrackedConfig {
+    /// Fills the lists unset here from `fallback`.
+    pub fn or(self, fallback: UntrackedConfig) -> UntrackedConfig {
+        UntrackedConfig {
+            copy: self.copy.or(fallback.copy),
+            symlink: self.symlink.or(fallback.symlink),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transfer {
+    Copy,
+    Symlink,
+}
+
+/// A file or directory of the main checkout to bring into a new worktree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UntrackedFile {
+    /// Relative to the working directory.
+    pub path: PathBuf,
+    pub transfer: Transfer,
+}
+
+/// The files in `workdir` matching the patterns of `config`, in the order of
+/// the patterns. A path matching both lists is linked. Nothing under `.git` is
+/// ever matched.
+pub fn find(workdir: &Path, config: &UntrackedConfig) -> eyre::Result<Vec<UntrackedFile>> {
+    let root = glob::Pattern::escape(&workdir.to_string_lossy());
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for (patterns, transfer, key) in [
+        (&config.symlink, Transfer::Symlink, "symlink"),
+        (&config.copy, Transfer::Copy, "copy"),
+    ] {
+        for pattern in patterns.iter().flatten() {
+            let matches = glob::glob(&format!("{}/{}", root, pattern))
+                .map_err(|e| eyre!("Invalid pattern {:?} in untracked.{}: {}", pattern, key, e))?;
+            for path in matches.flatten() {
+                let Ok(relative) = path.strip_prefix(workdir) else {
+                    continue;
+                };
+                let in_git_dir = relative
+                    .components()
+                    .any(|c| c == Component::Normal(".git".as_ref()));
+                if in_git_dir || relative.as_os_str().is_empty() {
+                    continue;
+                }
+                if seen.insert(relative.to_path_buf()) {
+                    files.push(UntrackedFile {
+                        path: relative.to_path_buf(),
+                        transfer,
+                    });
+                }
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Copies or links `files` from `workdir` into `worktree_dir`, carrying on past
+/// failures, and returns their errors. Paths that already exist in the
+/// worktree, such as tracked files, are left alone.
+pub fn bring_into(workdir: &Path, worktree_dir: &Path, files: &[UntrackedFile]) -> Vec<String> {
+    files
+        .iter()
+        .filter_map(|file| {
+            let source = workdir.join(&file.path);
+            let destination = worktree_dir.join(&file.path);
+            if destination.symlink_metadata().is_ok() {
+                return None;
+            }
+            let result = match file.transfer {
+                Transfer::Copy => copy(&source, &destination),
+                Transfer::Symlink => symlink(&source, &destination),
+            };
+            result
+                .wrap_err_with(|| format!("Could not {} {:?}", verb(file.transfer), file.path))
+                .err()
+                .map(|e| format!("{:#}", e))
+        })
+        .collect()
+}
+
+fn verb(transfer: Transfer) -> &'static str {
+    match transfer {
+        Transfer::Copy => "copy",
+        Transfer::Symlink => "link",
+    }
+}
+
+fn copy(source: &Path, destination: &Path) -> io::Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let metadata = source.symlink_metadata()?;
+    if metadata.is_symlink() {
+        symlink(&fs::read_link(source)?, destination)
+    } else if metadata.is_dir() {
+        fs::create_dir(destination)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(source, destination).map(|_| ())
+    }
+}
+
+fn symlink(source: &Path, destination: &Path) -> io::Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    #[cfg(unix)]
+    return std::os::unix::fs::symlink(source, destination);
+    #[cfg(windows)]
+    return if source.is_dir() {
+        std::os::windows::fs::symlink_dir(source, destination)
+    } else {
+        std::os::windows::fs::symlink_file(source, destination)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_bring_into() {
+        let workdir = tempfile::tempdir().unwrap();
+        let worktree = tempfile::tempdir().unwrap();
+        for file in [
+            ".env",
+            ".env.local",
+            "README.md",
+            "certs/dev.pem",
+            "node_modules/left-pad/index.js",
+            ".git/config",
+        ] {
+            let path = workdir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, file).unwrap();
+        }
+        fs::write(worktree.path().join("README.md"), "tracked").unwrap();
+
+        let config: UntrackedConfig = toml::from_str(
+            r#"
+            copy = [".env*", "certs", "README.md", "node_modules", ".git/*"]
+            symlink = ["node_modules"]
+            "#,
+        )
+        .unwrap();
+        let files = find(workdir.path(), &config).unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path.to_str().unwrap()).collect();
+        assert_eq!(
+            paths,
+            ["node_modules", ".env", ".env.local", "certs", "README.md"]
+        );
+        assert_eq!(files[0].transfer, Transfer::Symlink);
+
+        let errors = bring_into(workdir.path(), worktree.path(), &files);
+        assert!(errors.is_empty(), "{:?}", errors);
+        let read = |file| fs::read_to_string(worktree.path().join(file)).unwrap();
+        assert_eq!(read(".env.local"), ".env.local");
+        assert_eq!(read("certs/dev.pem"), "certs/dev.pem");
+        assert_eq!(read("README.md"), "tracked");
+        assert!(worktree
+            .path()
+            .join("node_modules")
+            .symlink_metadata()
+            .unwrap()
+            .is_symlink());
+
+        let config: UntrackedConfig = toml::from_str(r#"copy = ["[oops"]"#).unwrap();
+        assert!(find(workdir.path(), &config).is_err());
+    }
+}
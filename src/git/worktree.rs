@@ -3,22 +3,35 @@ use color_eyre::eyre::WrapErr;
 use git2::Repository;
 use std::{
     fs::{self},
-    path::Path,
+    path::{Path, PathBuf},
 };
 use tracing::debug;
 
-#[derive(Clone, Copy)]
+use super::repository::{
+    default_branch_name, fetch_with_prune, is_worktree_dirty, remote_callbacks,
+    remote_status_of_branch,
+};
+
+/// Named as in the `status:` filter qualifier on the command line.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
 pub enum RemoteStatus {
     /// Upstream is configured and the remote tracking ref exists.
+    #[value(name = "pushed")]
     Exists,
     /// Upstream was configured but the remote tracking ref is gone (merged/deleted).
     Gone,
     /// No upstream has ever been configured (never pushed).
+    #[value(name = "unpushed")]
     NeverPushed,
 }
 
 pub struct Worktree {
     pub git_worktree: git2::Worktree,
+    /// Branch checked out in the worktree. Usually differs from the worktree
+    /// name when the branch contains a `/`.
+    pub branch: String,
+    /// Time of the HEAD commit, in seconds since the Unix epoch.
+    pub last_commit_time: Option<i64>,
     pub remote_status: RemoteStatus,
     pub is_dirty: bool,
 }
@@ -35,6 +48,207 @@ impl Worktree {
             .name()
             .expect("Could not get worktree name")
     }
+
+    /// Short name of the branch checked out in `git_worktree`, `None` when
+    /// HEAD is detached.
+    pub(super) fn head_branch(git_worktree: &git2::Worktree) -> Option<String> {
+        let repo = Repository::open_from_worktree(git_worktree).ok()?;
+        let head = repo.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+        head.shorthand().map(|name| name.to_string())
+    }
+
+    pub(super) fn head_commit_time(git_worktree: &git2::Worktree) -> Option<i64> {
+        let repo = Repository::open_from_worktree(git_worktree).ok()?;
+        let commit = repo.head().ok()?.peel_to_commit().ok()?;
+        Some(commit.time().seconds())
+    }
+
+    /// Name of the repository this worktree belongs to, as shown in the
+    /// repositories list.
+    pub fn repository_name(&self) -> Option<String> {
+        let path = self.repository_path()?;
+        Some(path.file_name()?.to_str()?.to_string())
+    }
+
+    /// Working directory of the repository the worktree belongs to.
+    pub fn repository_path(&self) -> Option<PathBuf> {
+        let repo = Repository::open_from_worktree(&self.git_worktree).ok()?;
+        let common_dir = repo.commondir().to_str()?.to_string();
+        Some(PathBuf::from(
+            common_dir.trim_end_matches('/').trim_end_matches("/.git"),
+        ))
+    }
+
+    /// URL of the `origin` remote of the repository this worktree belongs to.
+    pub fn origin_url(&self) -> Option<String> {
+        let repo = Repository::open_from_worktree(&self.git_worktree).ok()?;
+        let remote = repo.find_remote("origin").ok()?;
+        remote.url().map(|url| url.to_string())
+    }
+
+    /// Short name of the default branch of the repository, e.g. "main".
+    pub fn default_base_branch(&self) -> Option<String> {
+        let repo = Repository::open_from_worktree(&self.git_worktree).ok()?;
+        default_branch_name(&repo)
+    }
+
+    /// Messages of the commits on this worktree's HEAD that are not on
+    /// `origin/<base_branch>`, oldest first.
+    pub fn commit_messages_since(&self, base_branch: &str) -> eyre::Result<Vec<String>> {
+        let repo = Repository::open_from_worktree(&self.git_worktree)
+            .wrap_err_with(|| format!("Could not open worktree '{}'", self.name()))?;
+        let head = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .wrap_err_with(|| format!("Could not resolve HEAD of worktree '{}'", self.name()))?;
+        let base = repo
+            .find_branch(&format!("origin/{}", base_branch), git2::BranchType::Remote)
+            .and_then(|branch| branch.get().peel_to_commit())
+            .wrap_err_with(|| format!("Could not resolve base branch 'origin/{}'", base_branch))?;
+        let merge_base = repo
+            .merge_base(head.id(), base.id())
+            .wrap_err("Could not find the merge base with the base branch")?;
+
+        let mut revwalk = repo
+            .revwalk()
+            .wrap_err("Could not walk the commit history")?;
+        revwalk
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)
+            .wrap_err("Could not walk the commit history")?;
+        revwalk
+            .push(head.id())
+            .and_then(|_| revwalk.hide(merge_base))
+            .wrap_err("Could not walk the commit history")?;
+
+        let mut messages = Vec::new();
+        for oid in revwalk {
+            let commit = oid.and_then(|oid| repo.find_commit(oid))?;
+            if let Some(message) = commit.message() {
+                messages.push(message.trim().to_string());
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Pushes the worktree's branch to `origin` and sets it as the upstream.
+    /// Returns the name of the pushed branch.
+    pub fn push_to_origin(&mut self) -> eyre::Result<String> {
+        let repo = Repository::open_from_worktree(&self.git_worktree)
+            .wrap_err_with(|| format!("Could not open worktree '{}'", self.name()))?;
+        let head = repo
+            .head()
+            .wrap_err_with(|| format!("Could not get HEAD of worktree '{}'", self.name()))?;
+        if !head.is_branch() {
+            eyre::bail!("HEAD of worktree '{}' is not a branch", self.name());
+        }
+        let refname = head.name().unwrap_or_default().to_string();
+        let branch_name = head.shorthand().unwrap_or_default().to_string();
+        let head_oid = head
+            .target()
+            .ok_or_else(|| eyre::eyre!("HEAD of worktree '{}' has no commit", self.name()))?;
+
+        let mut remote = repo
+            .find_remote("origin")
+            .wrap_err("Could not find remote 'origin'")?;
+        let mut rejection: Option<String> = None;
+        {
+            let mut callbacks = remote_callbacks();
+            callbacks.push_update_reference(|_refname, status| {
+                rejection = status.map(|s| s.to_string());
+                Ok(())
+            });
+            let mut push_options = git2::PushOptions::new();
+            push_options.remote_callbacks(callbacks);
+            remote
+                .push(
+                    &[format!("{}:{}", refname, refname)],
+                    Some(&mut push_options),
+                )
+                .wrap_err_with(|| format!("Could not push branch '{}'", branch_name))?;
+        }
+        if let Some(reason) = rejection {
+            eyre::bail!("Push of branch '{}' was rejected: {}", branch_name, reason);
+        }
+
+        // Record the remote tracking ref like `git push` does, so the upstream can be set.
+        let tracking_ref = format!("refs/remotes/origin/{}", branch_name);
+        repo.reference(&tracking_ref, head_oid, true, "shanti: push")
+            .wrap_err_with(|| format!("Could not update '{}'", tracking_ref))?;
+        repo.find_branch(&branch_name, git2::BranchType::Local)
+            .and_then(|mut branch| branch.set_upstream(Some(&format!("origin/{}", branch_name))))
+            .wrap_err_with(|| format!("Could not set upstream for branch '{}'", branch_name))?;
+
+        self.remote_status = RemoteStatus::Exists;
+        Ok(branch_name)
+    }
+
+    /// Updates the remote status from the remote-tracking branches, e.g. after
+    /// another worktree of the same repository was fetched.
+    pub fn refresh_remote_status(&mut self) {
+        if let Ok(repo) = Repository::open_from_worktree(&self.git_worktree) {
+            self.update_remote_status(&repo);
+        }
+    }
+
+    fn update_remote_status(&mut self, repo: &Repository) {
+        if let Ok(head) = repo.head() {
+            if head.is_branch() {
+                self.remote_status = remote_status_of_branch(repo, &git2::Branch::wrap(head));
+            }
+        }
+    }
+}
+
+/// Fetches `origin` of the worktree at `path` with pruning. Takes a path, as
+/// a `Worktree` can't be sent to the thread fetching in the background;
+/// `Worktree::refresh_remote_status` picks up the result.
+pub fn fetch_worktree(path: &str) -> eyre::Result<()> {
+    let repo =
+        Repository::open(path).wrap_err_with(|| format!("Could not open worktree {:?}", path))?;
+    fetch_with_prune(&repo, "origin").wrap_err("Could not fetch from origin")
+}
+
+/// Fetches `origin` and fast-forwards the branch of the worktree at `path` to
+/// its upstream. Returns what was done, e.g. "already up to date".
+pub fn pull_worktree(path: &str) -> eyre::Result<String> {
+    if is_worktree_dirty(path) {
+        eyre::bail!("has uncommitted changes");
+    }
+    let repo =
+        Repository::open(path).wrap_err_with(|| format!("Could not open worktree {:?}", path))?;
+    fetch_with_prune(&repo, "origin").wrap_err("Could not fetch from origin")?;
+
+    let head = repo.head().wrap_err("Could not get HEAD")?;
+    if !head.is_branch() {
+        eyre::bail!("HEAD is not a branch");
+    }
+    let upstream = git2::Branch::wrap(head)
+        .upstream()
+        .wrap_err("Branch has no upstream")?;
+    let upstream_commit = repo
+        .reference_to_annotated_commit(upstream.get())
+        .wrap_err("Could not resolve the upstream branch")?;
+    let (analysis, _) = repo
+        .merge_analysis(&[&upstream_commit])
+        .wrap_err("Could not compare with the upstream branch")?;
+    if analysis.is_up_to_date() {
+        return Ok("already up to date".to_string());
+    }
+    if !analysis.is_fast_forward() {
+        eyre::bail!("cannot fast-forward, the branch has diverged");
+    }
+
+    let target = upstream_commit.id();
+    let object = repo.find_object(target, None)?;
+    repo.checkout_tree(&object, Some(git2::build::CheckoutBuilder::new().safe()))
+        .wrap_err("Could not check out the upstream commit")?;
+    repo.head()?
+        .set_target(target, "shanti: fast-forward")
+        .wrap_err("Could not move the branch")?;
+    Ok(format!("fast-forwarded to {:.7}", target))
 }
 
 pub fn delete_worktree(worktree: &Worktree) -> eyre::Result<()> {
@@ -1,20 +1,83 @@
+use chrono::{DateTime, Local, TimeDelta};
 use color_eyre::eyre::{self, WrapErr};
-use std::io;
-use std::process::Command;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
 
-#[derive(Clone)]
+use crate::git;
+
+#[derive(Clone, Debug)]
 pub struct PrUrl {
     pub owner: String,
     pub repo: String,
     pub number: u64,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct PrInfo {
     pub branch_name: String,
     pub is_merged: bool,
 }
 
+pub struct NewPullRequest {
+    pub title: String,
+    pub body: String,
+    pub head: String,
+    pub base: String,
+    pub draft: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PrState {
+    Open,
+    Draft,
+    Merged,
+    Closed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ReviewDecision {
+    Approved,
+    ChangesRequested,
+    ReviewRequired,
+}
+
+/// Combined state of the status checks on the PR's head commit.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CiStatus {
+    Passing,
+    Pending,
+    Failing,
+}
+
+/// Dashboard view of the most recent PR opened from a branch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PrStatus {
+    pub number: u64,
+    pub state: PrState,
+    pub review: Option<ReviewDecision>,
+    pub ci: Option<CiStatus>,
+}
+
+const PR_STATUS_QUERY: &str = "\
+query($owner: String!, $repo: String!, $branch: String!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequests(headRefName: $branch, first: 1, orderBy: {field: CREATED_AT, direction: DESC}) {
+      nodes {
+        number
+        state
+        isDraft
+        reviewDecision
+        commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+      }
+    }
+  }
+}";
+
 /// Parses a GitHub PR URL of the form:
 /// `https://github.com/{owner}/{repo}/pull/{number}`
 pub fn parse_pr_url(url: &str) -> eyre::Result<PrUrl> {
@@ -38,90 +101,505 @@ pub fn parse_pr_url(url: &str) -> eyre::Result<PrUrl> {
     })
 }
 
-/// Fetches PR info. Authentication priority:
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Talks to the GitHub API. Authentication priority:
 /// 1. `gh api` — uses `GITHUB_TOKEN` env var if set (fine-grained PAT), otherwise `gh` stored credentials
 /// 2. `ureq` with `GITHUB_TOKEN` — pure-Rust fallback when `gh` CLI is not installed
-pub fn fetch_pr_info(pr: &PrUrl) -> eyre::Result<PrInfo> {
-    let endpoint = format!("/repos/{}/{}/pulls/{}", pr.owner, pr.repo, pr.number);
+#[derive(Clone)]
+pub struct GithubClient {
+    api_url: String,
+    gh_program: Option<String>,
+    token: Option<String>,
+}
 
-    match Command::new("gh").args(["api", &endpoint]).output() {
-        Ok(output) if output.status.success() => {
-            return parse_pr_json(&output.stdout);
+impl GithubClient {
+    /// `gh_program` is the `gh` executable to try first; `None` always goes
+    /// straight to `api_url` with `token`.
+    pub fn new(api_url: String, gh_program: Option<String>, token: Option<String>) -> Self {
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            gh_program,
+            token,
         }
-        Ok(output) => {
-            // gh is installed but the request failed
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stderr = stderr.trim();
-            if stderr.contains("authentication")
-                || stderr.contains("not logged")
-                || stderr.is_empty()
-            {
-                eyre::bail!("GitHub auth failed — set GITHUB_TOKEN or run `gh auth login`");
+    }
+
+    /// Builds the client from the environment. `SHANTI_GITHUB_API_URL` points
+    /// the client at another API (e.g. GitHub Enterprise or a local stub); `gh`
+    /// only knows about github.com, so it is skipped in that case.
+    pub fn from_env() -> Self {
+        let token = std::env::var("GITHUB_TOKEN").ok();
+        match std::env::var("SHANTI_GITHUB_API_URL") {
+            Ok(api_url) => Self::new(api_url, None, token),
+            Err(_) => Self::new(DEFAULT_API_URL.to_string(), Some("gh".to_string()), token),
+        }
+    }
+
+    pub fn fetch_pr_info(&self, pr: &PrUrl) -> eyre::Result<PrInfo> {
+        let endpoint = format!("/repos/{}/{}/pulls/{}", pr.owner, pr.repo, pr.number);
+        let response = self.send("GET", &endpoint, None)?;
+        check_response(&response, &pr.owner, &pr.repo)?;
+        parse_pr_json(&response.body)
+    }
+
+    /// Opens a pull request and returns its URL.
+    pub fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr: &NewPullRequest,
+    ) -> eyre::Result<String> {
+        let endpoint = format!("/repos/{}/{}/pulls", owner, repo);
+        let payload = serde_json::json!({
+            "title": pr.title,
+            "body": pr.body,
+            "head": pr.head,
+            "base": pr.base,
+            "draft": pr.draft,
+        });
+        let response = self.send("POST", &endpoint, Some(&payload))?;
+        check_response(&response, owner, repo)?;
+        parse_created_pr_json(&response.body)
+    }
+
+    /// Looks up the most recent PR whose head is `branch` in `owner/repo`.
+    /// Returns `None` when no PR has been opened from the branch.
+    pub fn fetch_pr_status(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> eyre::Result<Option<PrStatus>> {
+        let payload = serde_json::json!({
+            "query": PR_STATUS_QUERY,
+            "variables": { "owner": owner, "repo": repo, "branch": branch },
+        });
+        let response = self.send("POST", "/graphql", Some(&payload))?;
+        check_response(&response, owner, repo)?;
+        check_graphql_response(&response, owner, repo)?;
+        parse_pr_status_json(&response.body)
+    }
+
+    /// Sends a request through `gh`, or straight to the API with `GITHUB_TOKEN`
+    /// when `gh` is not available. Error statuses are returned as responses, so
+    /// [`check_response`] can tell them apart from their status and headers.
+    fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        payload: Option<&serde_json::Value>,
+    ) -> eyre::Result<ApiResponse> {
+        let Some(gh) = &self.gh_program else {
+            return self.request(method, endpoint, payload);
+        };
+
+        let mut args = vec!["api", "--include", "--method", method];
+        args.push(endpoint.trim_start_matches('/'));
+        if payload.is_some() {
+            args.extend(["--input", "-"]);
+        }
+        let spawned = Command::new(gh)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            // gh not installed — fall back to ureq
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return self.request(method, endpoint, payload);
             }
-            eyre::bail!("GitHub API error: {}", stderr);
+            Err(e) => return Err(e).wrap_err("Failed to run gh"),
+        };
+        if let (Some(mut stdin), Some(payload)) = (child.stdin.take(), payload) {
+            stdin
+                .write_all(payload.to_string().as_bytes())
+                .wrap_err("Failed to send the request to gh")?;
+        }
+        let output = child.wait_with_output().wrap_err("Failed to run gh")?;
+
+        // With --include gh prints the status line and headers of any response it
+        // got, even on HTTP errors, so an empty stdout means the request never
+        // reached GitHub or gh refused to send it.
+        if let Some(response) = parse_gh_output(&output.stdout) {
+            return Ok(response);
         }
-        Err(e) if e.kind() == io::ErrorKind::NotFound => {
-            // gh not installed — fall through to ureq fallback
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        if stderr.is_empty()
+            || stderr.contains("auth login")
+            || stderr.contains("authentication")
+            || stderr.contains("not logged")
+        {
+            return Err(GithubError::Auth(stderr).into());
         }
-        Err(e) => eyre::bail!("Failed to run gh: {}", e),
+        Err(GithubError::Network(stderr).into())
+    }
+
+    /// Sends a request to the API with `GITHUB_TOKEN`.
+    fn request(
+        &self,
+        method: &str,
+        endpoint: &str,
+        payload: Option<&serde_json::Value>,
+    ) -> eyre::Result<ApiResponse> {
+        let token = self.token.as_deref().ok_or(GithubError::NoCredentials)?;
+        let url = format!("{}{}", self.api_url, endpoint);
+        let authorization = format!("Bearer {}", token);
+
+        let response = match payload {
+            Some(payload) if method == "POST" => ureq::post(&url)
+                .config()
+                .http_status_as_error(false)
+                .build()
+                .header("Authorization", &authorization)
+                .header("Accept", "application/vnd.github+json")
+                .header("User-Agent", "shanti")
+                .send_json(payload),
+            _ => ureq::get(&url)
+                .config()
+                .http_status_as_error(false)
+                .build()
+                .header("Authorization", &authorization)
+                .header("Accept", "application/vnd.github+json")
+                .header("User-Agent", "shanti")
+                .call(),
+        }
+        .map_err(|e| GithubError::Network(e.to_string()))?;
+
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let value = value.to_str().unwrap_or_default();
+                (name.as_str().to_string(), value.to_string())
+            })
+            .collect();
+        let body = response
+            .into_body()
+            .read_to_vec()
+            .map_err(|e| GithubError::Network(e.to_string()))?;
+
+        Ok(ApiResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+struct ApiResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl ApiResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The `message` GitHub puts in error bodies, followed by the first
+    /// validation error if any, e.g. "Validation Failed — A pull request
+    /// already exists for owner:branch."
+    fn message(&self) -> String {
+        let json: serde_json::Value = serde_json::from_slice(&self.body).unwrap_or_default();
+        let message = json["message"]
+            .as_str()
+            .map(|msg| msg.to_string())
+            .unwrap_or_else(|| format!("HTTP {}", self.status));
+        match json["errors"][0]["message"].as_str() {
+            Some(detail) => format!("{} — {}", message, detail),
+            None => message,
+        }
+    }
+
+    /// When the rate limit resets, from `x-ratelimit-reset` (primary limit) or
+    /// `retry-after` (secondary limit).
+    fn rate_limit_reset(&self) -> Option<DateTime<Local>> {
+        if let Some(reset) = self.header("x-ratelimit-reset") {
+            let timestamp = reset.trim().parse().ok()?;
+            return DateTime::from_timestamp(timestamp, 0).map(|reset| reset.with_timezone(&Local));
+        }
+        let seconds = self.header("retry-after")?.trim().parse().ok()?;
+        Some(Local::now() + TimeDelta::seconds(seconds))
+    }
+}
+
+/// Why a GitHub API request failed, worded so the user knows what to do next.
+#[derive(Debug)]
+pub enum GithubError {
+    /// Neither `gh` nor `GITHUB_TOKEN` is available.
+    NoCredentials,
+    /// The token is missing, invalid or expired.
+    Auth(String),
+    /// The token is valid but not allowed to read or write `owner`'s repository.
+    Forbidden {
+        owner: String,
+        message: String,
+    },
+    /// GitHub answers 404 for private repositories the token cannot see, so
+    /// this also covers missing access.
+    NotFound {
+        owner: String,
+        repo: String,
+    },
+    /// The organization enforces SAML SSO and the token is not authorized for it.
+    SsoRequired {
+        org: String,
+        url: Option<String>,
+    },
+    RateLimited {
+        reset: Option<DateTime<Local>>,
+    },
+    /// The request never got a response.
+    Network(String),
+    Api {
+        status: u16,
+        message: String,
+    },
+}
+
+impl fmt::Display for GithubError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GithubError::NoCredentials => write!(
+                f,
+                "GitHub CLI (gh) not found and GITHUB_TOKEN not set\n\
+                 Install gh: https://cli.github.com  or  set GITHUB_TOKEN"
+            ),
+            GithubError::Auth(msg) if msg.is_empty() => {
+                write!(
+                    f,
+                    "GitHub auth failed — set GITHUB_TOKEN or run `gh auth login`"
+                )
+            }
+            GithubError::Auth(msg) => write!(
+                f,
+                "GitHub auth failed ({}) — set GITHUB_TOKEN or run `gh auth login`",
+                msg
+            ),
+            GithubError::Forbidden { owner, message } => {
+                write!(f, "Token lacks access to {} — {}", owner, message)
+            }
+            GithubError::NotFound { owner, repo } => write!(
+                f,
+                "Not Found — {}/{} does not exist or the token lacks access to it",
+                owner, repo
+            ),
+            GithubError::SsoRequired {
+                org,
+                url: Some(url),
+            } => write!(
+                f,
+                "Token lacks access to org {} — authorize it for SSO: {}",
+                org, url
+            ),
+            GithubError::SsoRequired { org, url: None } => write!(
+                f,
+                "Token lacks access to org {} — authorize it for SSO in your token settings",
+                org
+            ),
+            GithubError::RateLimited { reset: Some(reset) } => write!(
+                f,
+                "GitHub API rate limit exceeded — rate limited until {}",
+                reset.format("%H:%M")
+            ),
+            GithubError::RateLimited { reset: None } => {
+                write!(f, "GitHub API rate limit exceeded — try again later")
+            }
+            GithubError::Network(msg) => write!(f, "Could not reach GitHub: {}", msg),
+            GithubError::Api { status, message } => {
+                write!(f, "GitHub API error ({}): {}", status, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GithubError {}
+
+/// Turns an error status into the matching [`GithubError`].
+fn check_response(response: &ApiResponse, owner: &str, repo: &str) -> Result<(), GithubError> {
+    if response.status < 400 {
+        return Ok(());
+    }
+    let message = response.message();
+
+    if response.status == 429
+        || response.header("x-ratelimit-remaining") == Some("0")
+        || response.header("retry-after").is_some()
+        || message.contains("rate limit")
+    {
+        return Err(GithubError::RateLimited {
+            reset: response.rate_limit_reset(),
+        });
+    }
+    // e.g. "x-github-sso: required; url=https://github.com/orgs/acme/sso?authorization_request=..."
+    if let Some(sso) = response.header("x-github-sso") {
+        let url = sso
+            .split_once("url=")
+            .map(|(_, url)| url.trim().to_string());
+        return Err(GithubError::SsoRequired {
+            org: url
+                .as_deref()
+                .and_then(sso_org)
+                .unwrap_or(owner)
+                .to_string(),
+            url,
+        });
+    }
+    if message.contains("SAML") {
+        return Err(GithubError::SsoRequired {
+            org: owner.to_string(),
+            url: None,
+        });
     }
 
-    // gh not available: try GITHUB_TOKEN with ureq
-    let token = std::env::var("GITHUB_TOKEN").map_err(|_| {
-        eyre::eyre!(
-            "GitHub CLI (gh) not found and GITHUB_TOKEN not set\n\
-             Install gh: https://cli.github.com  or  set GITHUB_TOKEN"
-        )
-    })?;
+    Err(match response.status {
+        401 => GithubError::Auth(message),
+        403 => GithubError::Forbidden {
+            owner: owner.to_string(),
+            message,
+        },
+        404 => GithubError::NotFound {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        },
+        status => GithubError::Api { status, message },
+    })
+}
+
+/// GraphQL reports most failures in `errors` of a 200 response.
+fn check_graphql_response(
+    response: &ApiResponse,
+    owner: &str,
+    repo: &str,
+) -> Result<(), GithubError> {
+    let json: serde_json::Value = serde_json::from_slice(&response.body).unwrap_or_default();
+    let error = &json["errors"][0];
+    let Some(message) = error["message"].as_str() else {
+        return Ok(());
+    };
+    Err(match error["type"].as_str() {
+        Some("RATE_LIMITED") => GithubError::RateLimited {
+            reset: response.rate_limit_reset(),
+        },
+        Some("NOT_FOUND") => GithubError::NotFound {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        },
+        _ if message.contains("SAML") => GithubError::SsoRequired {
+            org: owner.to_string(),
+            url: None,
+        },
+        Some("FORBIDDEN") => GithubError::Forbidden {
+            owner: owner.to_string(),
+            message: message.to_string(),
+        },
+        _ => GithubError::Api {
+            status: response.status,
+            message: message.to_string(),
+        },
+    })
+}
 
-    fetch_via_ureq(pr, &token)
+/// Extracts the org from an SSO authorization URL such as
+/// `https://github.com/orgs/acme/sso?authorization_request=...`.
+fn sso_org(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("/orgs/")?;
+    rest.split('/').next().filter(|org| !org.is_empty())
 }
 
-fn fetch_via_ureq(pr: &PrUrl, token: &str) -> eyre::Result<PrInfo> {
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}",
-        pr.owner, pr.repo, pr.number
-    );
+/// Splits the output of `gh api --include` into status, headers and body.
+fn parse_gh_output(stdout: &[u8]) -> Option<ApiResponse> {
+    let text = String::from_utf8_lossy(stdout);
+    let mut lines = text.split('\n');
+    // e.g. "HTTP/2.0 404 Not Found"
+    let status = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+
+    let mut headers = Vec::new();
+    let mut header_len = 0;
+    for line in text.split_inclusive('\n') {
+        header_len += line.len();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
 
-    let response = ureq::get(&url)
-        .header("Authorization", &format!("Bearer {}", token))
-        .header("Accept", "application/vnd.github+json")
-        .header("User-Agent", "shanti")
-        .call()
-        .wrap_err("GitHub API request failed")?;
+    Some(ApiResponse {
+        status,
+        headers,
+        body: stdout[header_len.min(stdout.len())..].to_vec(),
+    })
+}
 
-    let bytes = response
-        .into_body()
-        .read_to_vec()
-        .wrap_err("Failed to read GitHub API response")?;
+/// Extracts `(owner, repo)` from a GitHub remote URL in SSH or HTTPS form.
+pub fn parse_remote_url(url: &str) -> Option<(String, String)> {
+    let path = url
+        .strip_prefix("git@github.com:")
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))
+        .or_else(|| url.strip_prefix("https://github.com/"))?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
 
-    parse_pr_json(&bytes)
+/// Protocol used to clone repositories from GitHub.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum CloneProtocol {
+    Ssh,
+    Https,
 }
 
-/// Clones a GitHub repository into `<repos_dir>/<repo>` using SSH.
-pub fn clone_repository(owner: &str, repo: &str, repos_dir: &str) -> eyre::Result<()> {
-    let url = format!("git@github.com:{}/{}.git", owner, repo);
-    let dest = format!("{}/{}", repos_dir, repo);
-    let output = Command::new("git")
-        .args(["clone", &url, &dest])
-        .output()
-        .wrap_err("Failed to run git clone")?;
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eyre::bail!("git clone failed: {}", stderr.trim());
+pub fn clone_url(owner: &str, repo: &str, protocol: CloneProtocol) -> String {
+    match protocol {
+        CloneProtocol::Ssh => format!("git@github.com:{}/{}.git", owner, repo),
+        CloneProtocol::Https => format!("https://github.com/{}/{}.git", owner, repo),
     }
-    Ok(())
+}
+
+pub enum CloneEvent {
+    Progress(git::CloneProgress),
+    Finished(eyre::Result<()>),
+}
+
+/// Clones a GitHub repository into `<repos_dir>/<repo>` on a background thread,
+/// reporting progress over the returned channel.
+pub fn clone_repository(
+    owner: &str,
+    repo: &str,
+    repos_dir: &str,
+    protocol: CloneProtocol,
+    partial: bool,
+) -> Receiver<CloneEvent> {
+    let url = clone_url(owner, repo, protocol);
+    let dest = PathBuf::from(repos_dir).join(repo);
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let result = git::clone_repository(&url, &dest, partial, &|progress| {
+            let _ = sender.send(CloneEvent::Progress(progress));
+        });
+        let _ = sender.send(CloneEvent::Finished(result));
+    });
+    receiver
 }
 
 fn parse_pr_json(bytes: &[u8]) -> eyre::Result<PrInfo> {
     let json: serde_json::Value =
         serde_json::from_slice(bytes).wrap_err("Failed to parse GitHub API response")?;
 
-    // GitHub returns {"message": "..."} on errors (e.g. 404, bad token)
-    if let Some(msg) = json["message"].as_str() {
-        eyre::bail!("GitHub API error: {}", msg);
-    }
-
     let branch_name = json["head"]["ref"]
         .as_str()
         .ok_or_else(|| eyre::eyre!("Unexpected GitHub API response: missing head.ref"))?
@@ -135,6 +613,59 @@ fn parse_pr_json(bytes: &[u8]) -> eyre::Result<PrInfo> {
     })
 }
 
+fn parse_created_pr_json(bytes: &[u8]) -> eyre::Result<String> {
+    let json: serde_json::Value =
+        serde_json::from_slice(bytes).wrap_err("Failed to parse GitHub API response")?;
+
+    json["html_url"]
+        .as_str()
+        .map(|url| url.to_string())
+        .ok_or_else(|| eyre::eyre!("Unexpected GitHub API response: missing html_url"))
+}
+
+fn parse_pr_status_json(bytes: &[u8]) -> eyre::Result<Option<PrStatus>> {
+    let json: serde_json::Value =
+        serde_json::from_slice(bytes).wrap_err("Failed to parse GitHub API response")?;
+
+    let pr = &json["data"]["repository"]["pullRequests"]["nodes"][0];
+    if pr.is_null() {
+        return Ok(None);
+    }
+
+    let number = pr["number"]
+        .as_u64()
+        .ok_or_else(|| eyre::eyre!("Unexpected GitHub API response: missing PR number"))?;
+
+    let state = match (pr["state"].as_str(), pr["isDraft"].as_bool()) {
+        (Some("MERGED"), _) => PrState::Merged,
+        (Some("CLOSED"), _) => PrState::Closed,
+        (Some("OPEN"), Some(true)) => PrState::Draft,
+        (Some("OPEN"), _) => PrState::Open,
+        _ => eyre::bail!("Unexpected GitHub API response: unknown PR state"),
+    };
+
+    let review = match pr["reviewDecision"].as_str() {
+        Some("APPROVED") => Some(ReviewDecision::Approved),
+        Some("CHANGES_REQUESTED") => Some(ReviewDecision::ChangesRequested),
+        Some("REVIEW_REQUIRED") => Some(ReviewDecision::ReviewRequired),
+        _ => None,
+    };
+
+    let ci = match pr["commits"]["nodes"][0]["commit"]["statusCheckRollup"]["state"].as_str() {
+        Some("SUCCESS") => Some(CiStatus::Passing),
+        Some("PENDING") | Some("EXPECTED") => Some(CiStatus::Pending),
+        Some("FAILURE") | Some("ERROR") => Some(CiStatus::Failing),
+        _ => None,
+    };
+
+    Ok(Some(PrStatus {
+        number,
+        state,
+        review,
+        ci,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +693,137 @@ mod tests {
     fn test_parse_pr_url_non_numeric_number() {
         assert!(parse_pr_url("https://github.com/owner/repo/pull/abc").is_err());
     }
+
+    #[test]
+    fn test_parse_remote_url() {
+        for url in [
+            "git@github.com:muzomer/shanti.git",
+            "ssh://git@github.com/muzomer/shanti.git",
+            "https://github.com/muzomer/shanti",
+            "https://github.com/muzomer/shanti.git",
+        ] {
+            assert_eq!(
+                parse_remote_url(url),
+                Some(("muzomer".to_string(), "shanti".to_string())),
+                "Could not parse {}",
+                url
+            );
+        }
+        assert_eq!(parse_remote_url("git@gitlab.com:muzomer/shanti.git"), None);
+    }
+
+    #[test]
+    fn test_parse_pr_status_json() {
+        let json = br#"{"data":{"repository":{"pullRequests":{"nodes":[{
+            "number": 42, "state": "OPEN", "isDraft": false,
+            "reviewDecision": "CHANGES_REQUESTED",
+            "commits": {"nodes": [{"commit": {"statusCheckRollup": {"state": "FAILURE"}}}]}
+        }]}}}}"#;
+        let status = parse_pr_status_json(json).unwrap().unwrap();
+        assert_eq!(status.number, 42);
+        assert_eq!(status.state, PrState::Open);
+        assert_eq!(status.review, Some(ReviewDecision::ChangesRequested));
+        assert_eq!(status.ci, Some(CiStatus::Failing));
+    }
+
+    #[test]
+    fn test_parse_created_pr_json() {
+        let json = br#"{"number": 7, "html_url": "https://github.com/owner/repo/pull/7"}"#;
+        assert_eq!(
+            parse_created_pr_json(json).unwrap(),
+            "https://github.com/owner/repo/pull/7"
+        );
+    }
+
+    fn response(status: u16, headers: &[(&str, &str)], body: &str) -> ApiResponse {
+        ApiResponse {
+            status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_check_response_validation_failed() {
+        let response = response(
+            422,
+            &[],
+            r#"{"message": "Validation Failed",
+                "errors": [{"message": "A pull request already exists for owner:branch."}]}"#,
+        );
+        let err = check_response(&response, "owner", "repo").unwrap_err();
+        assert!(matches!(err, GithubError::Api { status: 422, .. }));
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn test_check_response_rate_limited() {
+        let response = response(
+            403,
+            &[
+                ("X-RateLimit-Remaining", "0"),
+                ("X-RateLimit-Reset", "1700000000"),
+            ],
+            r#"{"message": "API rate limit exceeded for 127.0.0.1."}"#,
+        );
+        let err = check_response(&response, "owner", "repo").unwrap_err();
+        let GithubError::RateLimited { reset: Some(reset) } = &err else {
+            panic!("Expected a rate limit with a reset time, got {:?}", err);
+        };
+        assert_eq!(reset.timestamp(), 1700000000);
+        assert!(err
+            .to_string()
+            .ends_with(&format!("rate limited until {}", reset.format("%H:%M"))));
+    }
+
+    #[test]
+    fn test_check_response_sso_required() {
+        let response = response(
+            403,
+            &[(
+                "x-github-sso",
+                "required; url=https://github.com/orgs/acme/sso?authorization_request=abc",
+            )],
+            r#"{"message": "Resource protected by organization SAML enforcement."}"#,
+        );
+        let err = check_response(&response, "owner", "repo").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Token lacks access to org acme"));
+    }
+
+    #[test]
+    fn test_check_response_status_codes() {
+        let not_found = response(404, &[], r#"{"message": "Not Found"}"#);
+        assert!(matches!(
+            check_response(&not_found, "owner", "repo"),
+            Err(GithubError::NotFound { .. })
+        ));
+        let bad_credentials = response(401, &[], r#"{"message": "Bad credentials"}"#);
+        assert!(matches!(
+            check_response(&bad_credentials, "owner", "repo"),
+            Err(GithubError::Auth(_))
+        ));
+        assert!(check_response(&response(200, &[], "{}"), "owner", "repo").is_ok());
+    }
+
+    #[test]
+    fn test_parse_gh_output() {
+        let stdout =
+            b"HTTP/2.0 404 Not Found\r\nX-Github-Request-Id: 1\r\n\r\n{\"message\":\"Not Found\"}";
+        let response = parse_gh_output(stdout).unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.header("x-github-request-id"), Some("1"));
+        assert_eq!(response.body, b"{\"message\":\"Not Found\"}");
+        assert!(parse_gh_output(b"").is_none());
+    }
+
+    #[test]
+    fn test_parse_pr_status_json_no_pr() {
+        let json = br#"{"data":{"repository":{"pullRequests":{"nodes":[]}}}}"#;
+        assert_eq!(parse_pr_status_json(json).unwrap(), None);
+    }
 }
@@ -0,0 +1,178 @@
+use color_eyre::eyre::{self, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::debug;
+
+use crate::dirs;
+
+const HISTORY_FILE: &str = "history.json";
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Order of the worktrees list when no filter is typed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortMode {
+    #[default]
+    Alphabetical,
+    MostRecent,
+    Frecency,
+    LastCommit,
+}
+
+impl SortMode {
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Alphabetical => SortMode::MostRecent,
+            SortMode::MostRecent => SortMode::Frecency,
+            SortMode::Frecency => SortMode::LastCommit,
+            SortMode::LastCommit => SortMode::Alphabetical,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Alphabetical => "a-z",
+            SortMode::MostRecent => "recent",
+            SortMode::Frecency => "frecent",
+            SortMode::LastCommit => "last commit",
+        }
+    }
+}
+
+/// How often and how recently a worktree was selected.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Usage {
+    pub count: u32,
+    /// Seconds since the Unix epoch.
+    pub last_used: u64,
+}
+
+impl Usage {
+    /// Selection count weighted by how recently the worktree was last used,
+    /// so a worktree used a lot last month ranks below one used today.
+    pub fn frecency(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_used);
+        let weight = if age < HOUR {
+            4.0
+        } else if age < DAY {
+            2.0
+        } else if age < WEEK {
+            0.5
+        } else {
+            0.25
+        };
+        self.count as f64 * weight
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct HistoryFile {
+    sort: SortMode,
+    /// Whether the worktrees are grouped by repository.
+    grouped: bool,
+    /// Usage per worktree path.
+    worktrees: HashMap<String, Usage>,
+}
+
+/// Worktree selections and the chosen sort mode and view, persisted in the data
+/// directory.
+#[derive(Default)]
+pub struct History {
+    data_dir: Option<PathBuf>,
+    file: HistoryFile,
+}
+
+impl History {
+    /// Loads the history kept in `data_dir`; it starts empty when there is none.
+    pub fn load(data_dir: Option<&Path>) -> Self {
+        let file = dirs::data_file(data_dir, HISTORY_FILE)
+            .and_then(|path| fs::read(path).wrap_err("Could not read history"))
+            .and_then(|bytes| serde_json::from_slice(&bytes).wrap_err("Could not parse history"))
+            .unwrap_or_else(|e| {
+                debug!("Starting with an empty history: {:#}", e);
+                HistoryFile::default()
+            });
+        Self {
+            data_dir: data_dir.map(Path::to_path_buf),
+            file,
+        }
+    }
+
+    pub fn sort_mode(&self) -> SortMode {
+        self.file.sort
+    }
+
+    pub fn set_sort_mode(&mut self, sort: SortMode) {
+        self.file.sort = sort;
+    }
+
+    pub fn grouped(&self) -> bool {
+        self.file.grouped
+    }
+
+    pub fn set_grouped(&mut self, grouped: bool) {
+        self.file.grouped = grouped;
+    }
+
+    pub fn usage(&self) -> &HashMap<String, Usage> {
+        &self.file.worktrees
+    }
+
+    pub fn record(&mut self, path: &str, now: u64) {
+        let usage = self.file.worktrees.entry(path.to_string()).or_default();
+        usage.count += 1;
+        usage.last_used = now;
+    }
+
+    pub fn remove(&mut self, path: &str) {
+        self.file.worktrees.remove(path);
+    }
+
+    pub fn save(&self) -> eyre::Result<()> {
+        let path = dirs::data_file(self.data_dir.as_deref(), HISTORY_FILE)?;
+        let bytes = serde_json::to_vec(&self.file).wrap_err("Could not serialize history")?;
+        fs::write(&path, bytes).wrap_err_with(|| format!("Could not write history to {:?}", path))
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frecency_prefers_recent_use() {
+        let now = 10 * WEEK;
+        let used_often_long_ago = Usage {
+            count: 10,
+            last_used: now - 2 * WEEK,
+        };
+        let used_once_today = Usage {
+            count: 1,
+            last_used: now - 10,
+        };
+        assert!(used_once_today.frecency(now) > used_often_long_ago.frecency(now));
+
+        let mut history = History::default();
+        history.record("/wt/a", now - DAY);
+        history.record("/wt/a", now);
+        assert_eq!(history.usage()["/wt/a"].count, 2);
+        assert_eq!(history.usage()["/wt/a"].last_used, now);
+        assert_eq!(history.usage()["/wt/a"].frecency(now), 8.0);
+    }
+}
@@ -0,0 +1,280 @@
+use color_eyre::eyre::{self, WrapErr};
+use serde::Deserialize;
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read},
+    path::Path,
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use crate::git::Worktree;
+
+/// File in the root of a repository with the hooks the repository itself
+/// suggests.
+pub const REPO_FILE: &str = ".shanti.toml";
+
+/// A `[hooks]` section, in `config.toml`, under `[repos.<name>]` or in a
+/// repository's `.shanti.toml`. Unset lists fall back to the next level; an
+/// empty list turns the hook off.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Shell commands run in a new worktree, e.g. `npm ci` or `direnv allow`.
+    pub post_create: Option<Vec<String>>,
+    /// Run in a worktree before it is deleted, e.g. `docker compose down`. A
+    /// failure keeps the worktree, unless the deletion is forced.
+    pub pre_delete: Option<Vec<String>>,
+    /// Run in the repository after a worktree is deleted, e.g. to drop its
+    /// database. Failures are reported; the worktree is gone either way.
+    pub post_delete: Option<Vec<String>>,
+}
+
+impl HooksConfig {
+    /// Fills the lists unset here from `fallback`.
+    pub fn or(self, fallback: HooksConfig) -> HooksConfig {
+        HooksConfig {
+            post_create: self.post_create.or(fallback.post_create),
+            pre_delete: self.pre_delete.or(fallback.pre_delete),
+            post_delete: self.post_delete.or(fallback.post_delete),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RepoFile {
+    hooks: HooksConfig,
+}
+
+/// Reads the `[hooks]` of `.shanti.toml` in `repo_path`, if there is one.
+pub fn load_repo_hooks(repo_path: &Path) -> eyre::Result<HooksConfig> {
+    let path = repo_path.join(REPO_FILE);
+    if !path.exists() {
+        return Ok(HooksConfig::default());
+    }
+    let text = fs::read_to_string(&path).wrap_err_with(|| format!("Could not read {:?}", path))?;
+    let file: RepoFile =
+        toml::from_str(&text).wrap_err_with(|| format!("Could not parse {:?}", path))?;
+    Ok(file.hooks)
+}
+
+/// The worktree a hook runs for, passed to it as `SHANTI_*` environment
+/// variables.
+#[derive(Clone, Debug)]
+pub struct HookContext {
+    pub hook: &'static str,
+    pub repo: String,
+    pub repo_path: String,
+    pub worktree_path: String,
+    pub branch: String,
+}
+
+impl HookContext {
+    pub fn for_worktree(worktree: &Worktree, hook: &'static str) -> Option<Self> {
+        let repo_path = worktree.repository_path()?;
+        Some(Self {
+            hook,
+            repo: worktree.repository_name()?,
+            repo_path: repo_path.to_string_lossy().to_string(),
+            worktree_path: worktree.path().to_string(),
+            branch: worktree.branch.clone(),
+        })
+    }
+
+    /// The worktree directory, or the repository once the worktree is gone.
+    fn dir(&self) -> &str {
+        if Path::new(&self.worktree_path).is_dir() {
+            &self.worktree_path
+        } else {
+            &self.repo_path
+        }
+    }
+
+    fn env(&self) -> [(&'static str, &str); 5] {
+        [
+            ("SHANTI_HOOK", self.hook),
+            ("SHANTI_REPO", &self.repo),
+            ("SHANTI_REPO_PATH", &self.repo_path),
+            ("SHANTI_WORKTREE_PATH", &self.worktree_path),
+            ("SHANTI_BRANCH", &self.branch),
+        ]
+    }
+}
+
+pub enum HookEvent {
+    Started(String),
+    Output(String),
+    /// A command failed, with the error.
+    Failed(String),
+    /// Every command of a background run ran, or one failed.
+    Finished,
+}
+
+/// Runs `commands` one after another on a background thread, streaming their
+/// output over the returned channel. Stops at the first failure unless
+/// `keep_going` is set.
+pub fn run_in_background(
+    commands: Vec<String>,
+    context: HookContext,
+    keep_going: bool,
+) -> Receiver<HookEvent> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let events = sender.clone();
+        run_all(&commands, &context, keep_going, move |event| {
+            let _ = events.send(event);
+        });
+        let _ = sender.send(HookEvent::Finished);
+    });
+    receiver
+}
+
+/// Runs `commands` one after another, reporting over `on_event`, and returns
+/// the errors of those that failed. Stops at the first failure unless
+/// `keep_going` is set.
+pub fn run_all(
+    commands: &[String],
+    context: &HookContext,
+    keep_going: bool,
+    on_event: impl Fn(HookEvent) + Send + Clone + 'static,
+) -> Vec<String> {
+    let mut errors = vec![];
+    for command in commands {
+        on_event(HookEvent::Started(command.clone()));
+        let output = on_event.clone();
+        if let Err(e) = run_command(command, context, move |line| {
+            output(HookEvent::Output(line))
+        }) {
+            let error = format!("{:#}", e);
+            on_event(HookEvent::Failed(error.clone()));
+            errors.push(error);
+            if !keep_going {
+                break;
+            }
+        }
+    }
+    errors
+}
+
+/// Runs `command` with `sh -c` in the worktree directory, or the repository
+/// once the worktree is deleted, and passes each line
+/// of its stdout and stderr to `on_line`.
+pub fn run_command(
+    command: &str,
+    context: &HookContext,
+    on_line: impl Fn(String) + Send + Clone + 'static,
+) -> eyre::Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(context.dir())
+        .envs(context.env())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .wrap_err_with(|| format!("Could not run `{}`", command))?;
+
+    let readers: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|r| Box::new(r) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|r| Box::new(r) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|reader| {
+        let on_line = on_line.clone();
+        thread::spawn(move || forward_lines(reader, on_line))
+    })
+    .collect();
+    for reader in readers {
+        let _ = reader.join();
+    }
+
+    let status = child
+        .wait()
+        .wrap_err_with(|| format!("Could not wait for `{}`", command))?;
+    if !status.success() {
+        eyre::bail!("`{}` failed with {}", command, status);
+    }
+    Ok(())
+}
+
+fn forward_lines(reader: Box<dyn Read + Send>, on_line: impl Fn(String)) {
+    for line in BufReader::new(reader).lines() {
+        match line {
+            Ok(line) => on_line(line),
+            Err(_) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(dir: &Path) -> HookContext {
+        HookContext {
+            hook: "post_create",
+            repo: "api".to_string(),
+            repo_path: "/repos/api".to_string(),
+            worktree_path: dir.to_str().unwrap().to_string(),
+            branch: "feat/login".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_run_command_passes_the_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        run_command(
+            "echo $SHANTI_REPO $SHANTI_BRANCH; pwd; echo oops >&2",
+            &context(dir.path()),
+            move |line| {
+                let _ = sender.send(line);
+            },
+        )
+        .unwrap();
+        let mut lines: Vec<String> = receiver.try_iter().collect();
+        lines.sort();
+        let cwd = dir.path().canonicalize().unwrap();
+        let mut expected = ["api feat/login", cwd.to_str().unwrap(), "oops"].map(String::from);
+        expected.sort();
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn test_keep_going_runs_every_hook() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut context = context(dir.path());
+        context.worktree_path = dir.path().join("deleted").to_str().unwrap().to_string();
+        context.repo_path = dir.path().to_str().unwrap().to_string();
+        let commands = ["false", "touch cleaned", "exit 2"].map(String::from);
+        let errors = run_all(&commands, &context, true, |_| {});
+        assert_eq!(errors.len(), 2);
+        // Runs in the repository, as the worktree directory is gone.
+        assert!(dir.path().join("cleaned").exists());
+    }
+
+    #[test]
+    fn test_failing_hook_stops_the_rest() {
+        let dir = tempfile::tempdir().unwrap();
+        let events = run_in_background(
+            vec!["exit 3".to_string(), "echo never".to_string()],
+            context(dir.path()),
+            false,
+        );
+        let events: Vec<HookEvent> = events.iter().collect();
+        assert!(matches!(&events[0], HookEvent::Started(c) if c == "exit 3"));
+        assert!(matches!(&events[1], HookEvent::Failed(error) if error.contains("exit status: 3")));
+        assert!(matches!(events[2], HookEvent::Finished));
+        assert_eq!(events.len(), 3);
+    }
+}
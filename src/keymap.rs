@@ -40,6 +40,43 @@ fn resolve_normal(key: KeyEvent) -> Option<Action> {
         (KeyCode::Char('P'), KeyModifiers::NONE) | (KeyCode::Char('P'), KeyModifiers::SHIFT) => {
             Some(Action::OpenPrWorktreeAutoClone)
         }
+        (KeyCode::Char('c'), KeyModifiers::NONE) => Some(Action::OpenCreatePr),
+        (KeyCode::Char('m'), KeyModifiers::NONE) => Some(Action::OpenMetadata),
+        (KeyCode::Char('t'), KeyModifiers::NONE) => Some(Action::OpenTicketWorktree),
+        (KeyCode::Char('s'), KeyModifiers::NONE) => Some(Action::CycleSort),
+        (KeyCode::Char('f'), KeyModifiers::NONE) => Some(Action::TogglePin),
+        (KeyCode::Char('v'), KeyModifiers::NONE) => Some(Action::ToggleGrouped),
+        (KeyCode::Char('h'), KeyModifiers::NONE) | (KeyCode::Left, KeyModifiers::NONE) => {
+            Some(Action::Collapse)
+        }
+        (KeyCode::Char('l'), KeyModifiers::NONE) | (KeyCode::Right, KeyModifiers::NONE) => {
+            Some(Action::Expand)
+        }
+        (KeyCode::Char(' '), KeyModifiers::NONE) => Some(Action::ToggleMark),
+        (KeyCode::Char('V'), KeyModifiers::NONE) | (KeyCode::Char('V'), KeyModifiers::SHIFT) => {
+            Some(Action::ToggleVisual)
+        }
+        (KeyCode::Char('A'), KeyModifiers::NONE) | (KeyCode::Char('A'), KeyModifiers::SHIFT) => {
+            Some(Action::ToggleMarkAll)
+        }
+        (KeyCode::Char('X'), KeyModifiers::NONE) | (KeyCode::Char('X'), KeyModifiers::SHIFT) => {
+            Some(Action::Prune)
+        }
+        (KeyCode::Char('F'), KeyModifiers::NONE) | (KeyCode::Char('F'), KeyModifiers::SHIFT) => {
+            Some(Action::Fetch)
+        }
+        (KeyCode::Char('u'), KeyModifiers::NONE) => Some(Action::Pull),
+        (KeyCode::Char('e'), KeyModifiers::NONE) => Some(Action::OpenInEditor),
+        (KeyCode::Char('y'), KeyModifiers::NONE) => Some(Action::CopyPath),
+        (KeyCode::Char('T'), KeyModifiers::NONE) | (KeyCode::Char('T'), KeyModifiers::SHIFT) => {
+            Some(Action::OpenTerminal)
+        }
+        (KeyCode::Char('!'), KeyModifiers::NONE) | (KeyCode::Char('!'), KeyModifiers::SHIFT) => {
+            Some(Action::RunCommand)
+        }
+        (KeyCode::Char('L'), KeyModifiers::NONE) | (KeyCode::Char('L'), KeyModifiers::SHIFT) => {
+            Some(Action::ToggleLog)
+        }
         (KeyCode::Esc, KeyModifiers::NONE) => Some(Action::ClosePopup),
         (KeyCode::Char('/'), KeyModifiers::NONE) | (KeyCode::Char('i'), KeyModifiers::NONE) => {
             Some(Action::EnterInsertMode)
@@ -58,6 +95,8 @@ fn resolve_insert(key: KeyEvent) -> Option<Action> {
             KeyCode::Char('c') => Some(Action::Quit),
             KeyCode::Char('j') | KeyCode::Char('n') => Some(Action::MoveDown),
             KeyCode::Char('k') | KeyCode::Char('p') => Some(Action::MoveUp),
+            KeyCode::Char('d') => Some(Action::ToggleDraft),
+            KeyCode::Char('s') => Some(Action::Submit),
             _ => None,
         };
     }
@@ -1,18 +1,35 @@
 pub mod app;
-mod cli;
+pub mod cli;
+mod clipboard;
+pub mod completions;
 mod components;
+pub mod config;
 mod dirs;
+pub mod exec;
 mod git;
-mod github;
+pub mod github;
+mod history;
+mod hooks;
 pub mod keymap;
 pub mod logs;
+mod metadata;
+mod open;
+mod pr_status;
+mod sessions;
+pub mod shell;
+mod theme;
+pub mod tracker;
 
-use std::io;
+use std::{io, time::Duration};
 
 use components::EventState;
 use ratatui::{
     backend::Backend,
-    crossterm::event::{self, Event},
+    crossterm::{
+        event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+        execute,
+        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    },
     Terminal,
 };
 
@@ -23,10 +40,48 @@ pub fn run_app<B: Backend>(
     loop {
         terminal.draw(|f| app.draw(f))?;
 
-        if let Event::Key(key) = event::read()? {
-            if app.handle_key(key) == EventState::Exit {
+        // Poll instead of blocking so background results get drawn without a key press.
+        if event::poll(Duration::from_millis(250))? {
+            let state = match event::read()? {
+                Event::Key(key) => app.handle_key(key),
+                Event::Mouse(mouse) => app.handle_mouse(mouse),
+                _ => EventState::NotConsumed,
+            };
+            if state == EventState::Exit {
                 break Ok(app.selected_path.take());
             }
-        };
+        }
+        while let Some(mut command) = app.take_foreground_command() {
+            let result = run_in_foreground(terminal, &mut command)?;
+            app.foreground_command_finished(&command, result);
+        }
+        app.on_tick();
     }
 }
+
+/// Hands the terminal to `command` until it exits, then takes it back. The
+/// outer result fails only when the terminal can't be restored.
+fn run_in_foreground<B: Backend>(
+    terminal: &mut Terminal<B>,
+    command: &mut std::process::Command,
+) -> io::Result<io::Result<std::process::ExitStatus>> {
+    disable_raw_mode()?;
+    execute!(io::stderr(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+    // stdout may be captured by `$(shanti)`, so hand the command the terminal itself.
+    #[cfg(unix)]
+    if let Ok(tty) = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+    {
+        if let (Ok(stdin), Ok(stdout)) = (tty.try_clone(), tty.try_clone()) {
+            command.stdin(stdin).stdout(stdout);
+        }
+    }
+    let result = command.status();
+    enable_raw_mode()?;
+    execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(result)
+}
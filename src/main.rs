@@ -1,39 +1,110 @@
-use shanti::{app, logs, run_app};
-use std::{error::Error, io};
+use clap::CommandFactory;
+use color_eyre::eyre::{self, WrapErr};
+use shanti::{app, cli, completions, exec, logs, run_app, shell};
+use std::{fs, io, process::ExitCode};
 
 use ratatui::{
     backend::CrosstermBackend,
     crossterm::{
+        event::{DisableMouseCapture, EnableMouseCapture},
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     },
     Terminal,
 };
 
-fn main() -> Result<(), Box<dyn Error>> {
+/// Exit codes: a worktree was selected, the user quit without selecting one,
+/// or shanti failed. `shanti exec` exits with `COMMAND_FAILED` when the
+/// command failed in a worktree.
+const SELECTED: u8 = 0;
+const CANCELLED: u8 = 1;
+const FAILED: u8 = 2;
+const COMMAND_FAILED: u8 = 3;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => ExitCode::from(code),
+        Err(e) => {
+            eprintln!("shanti: {:#}", e);
+            ExitCode::from(FAILED)
+        }
+    }
+}
+
+fn run() -> eyre::Result<u8> {
+    completions::complete_if_requested();
+    let mut args = cli::Args::try_new()?;
+    if let Some(command) = args.command.take() {
+        match command {
+            cli::Command::Init { shell, bind } => print!("{}", shell::init_script(shell, bind)),
+            cli::Command::Completions { shell } => print!("{}", completions::script(shell)?),
+            cli::Command::Man => clap_mangen::Man::new(cli::Args::command())
+                .render(&mut io::stdout())
+                .wrap_err("Could not write the man page")?,
+            cli::Command::Exec {
+                repo,
+                filter,
+                status,
+                parallel,
+                command,
+            } => {
+                let selection = exec::Selection {
+                    repo,
+                    filter,
+                    status,
+                };
+                let targets = exec::targets(&args, &selection);
+                let passed = exec::run(&exec::command_line(&command), targets, parallel.into())?;
+                return Ok(if passed { SELECTED } else { COMMAND_FAILED });
+            }
+        }
+        return Ok(SELECTED);
+    }
+
     logs::initialize_logging()?;
-    let mut app = app::App::new();
-    let mut terminal = setup_terminal()?;
+    let print0 = args.print0;
+    let output_file = args.output_file.clone();
+    let mut app = app::App::from_args(args);
+    let mut terminal = setup_terminal().wrap_err("Could not set up the terminal")?;
     let selected = run_app(&mut terminal, &mut app);
-    let _ = restore_terminal(&mut terminal);
-    let path = match selected {
-        Ok(Some(path)) => path,
-        _ => ".".to_string(),
-    };
-    println!("{}", path);
-    Ok(())
+    // Errors are printed after this, so they don't end up on the alternate screen.
+    let restored = restore_terminal(&mut terminal);
+    let selected = selected.wrap_err("The terminal failed")?;
+    restored.wrap_err("Could not restore the terminal")?;
+
+    let terminator = if print0 { "\0" } else { "\n" };
+    match (&output_file, &selected) {
+        (Some(file), path) => {
+            let text = path.as_ref().map(|p| format!("{}{}", p, terminator));
+            fs::write(file, text.unwrap_or_default())
+                .wrap_err_with(|| format!("Could not write the selected path to {:?}", file))?;
+        }
+        (None, Some(path)) => print!("{}{}", path, terminator),
+        // `.` keeps a plain `cd $(shanti)` where it is.
+        (None, None) if !print0 => println!("."),
+        (None, None) => {}
+    }
+    Ok(if selected.is_some() {
+        SELECTED
+    } else {
+        CANCELLED
+    })
 }
 
 fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stderr>>> {
     enable_raw_mode()?;
     let mut stderr = io::stderr();
-    execute!(stderr, EnterAlternateScreen)?;
+    execute!(stderr, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stderr);
     Terminal::new(backend)
 }
 
 fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stderr>>) -> io::Result<()> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen,)?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
     terminal.show_cursor()
 }
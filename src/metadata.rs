@@ -0,0 +1,177 @@
+use color_eyre::eyre::{self, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+use crate::{dirs, tracker};
+
+const METADATA_FILE: &str = "worktree_metadata.json";
+
+/// User-provided details about a worktree that git does not track.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorktreeMetadata {
+    pub notes: String,
+    /// Links to tickets, e.g. JIRA issues or GitHub issues.
+    pub tickets: Vec<String>,
+    pub tags: Vec<String>,
+    /// Pinned worktrees are listed first and cannot be deleted until unpinned.
+    pub pinned: bool,
+}
+
+impl WorktreeMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.notes.trim().is_empty()
+            && self.tickets.is_empty()
+            && self.tags.is_empty()
+            && !self.pinned
+    }
+
+    /// Short id of the first ticket, shown in the worktree list.
+    pub fn ticket_id(&self) -> Option<String> {
+        self.tickets.first().map(|url| ticket_id(url))
+    }
+}
+
+/// Derives a short id from a ticket URL: `#42` for numbered issues such as
+/// `https://github.com/owner/repo/issues/42`, the issue key for trackers like
+/// JIRA or Linear, e.g. `PROJ-123` for
+/// `https://acme.atlassian.net/browse/PROJ-123`, and otherwise the last path
+/// segment.
+pub fn ticket_id(url: &str) -> String {
+    let last_segment = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(url)
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(url);
+    if !last_segment.is_empty() && last_segment.chars().all(|c| c.is_ascii_digit()) {
+        return format!("#{}", last_segment);
+    }
+    tracker::parse_issue_key(url).unwrap_or_else(|_| last_segment.to_string())
+}
+
+/// Metadata of every worktree, keyed by repository and worktree name and
+/// persisted in the data directory.
+#[derive(Default)]
+pub struct MetadataStore {
+    data_dir: Option<PathBuf>,
+    entries: BTreeMap<String, WorktreeMetadata>,
+}
+
+impl MetadataStore {
+    /// Loads the metadata saved in `data_dir`.
+    pub fn load(data_dir: Option<&Path>) -> Self {
+        let entries = dirs::data_file(data_dir, METADATA_FILE)
+            .and_then(|path| fs::read(path).wrap_err("Could not read worktree metadata"))
+            .and_then(|bytes| {
+                serde_json::from_slice(&bytes).wrap_err("Could not parse worktree metadata")
+            })
+            .unwrap_or_else(|e| {
+                debug!("Starting without worktree metadata: {:#}", e);
+                BTreeMap::new()
+            });
+        Self {
+            data_dir: data_dir.map(Path::to_path_buf),
+            entries,
+        }
+    }
+
+    pub fn get(&self, repo: &str, worktree: &str) -> Option<&WorktreeMetadata> {
+        self.entries.get(&key(repo, worktree))
+    }
+
+    /// Stores `metadata` for the worktree, dropping the entry when it is empty.
+    pub fn set(&mut self, repo: &str, worktree: &str, metadata: WorktreeMetadata) {
+        if metadata.is_empty() {
+            self.entries.remove(&key(repo, worktree));
+        } else {
+            self.entries.insert(key(repo, worktree), metadata);
+        }
+    }
+
+    /// Drops the entries of `repos` whose worktree is not in `worktrees`, given
+    /// as `(repo, worktree)` pairs. Entries of other repositories are kept, as
+    /// they may live in a repos directory that is not scanned this time.
+    pub fn remove_orphans(&mut self, repos: &[String], worktrees: &[(String, String)]) {
+        let live: HashSet<String> = worktrees
+            .iter()
+            .map(|(repo, worktree)| key(repo, worktree))
+            .collect();
+        self.entries.retain(|entry_key, _| {
+            let repo = entry_key.split_once('/').map(|(repo, _)| repo);
+            live.contains(entry_key) || !repo.is_some_and(|repo| repos.iter().any(|r| r == repo))
+        });
+    }
+
+    pub fn save(&self) -> eyre::Result<()> {
+        let path = dirs::data_file(self.data_dir.as_deref(), METADATA_FILE)?;
+        let bytes = serde_json::to_vec_pretty(&self.entries)
+            .wrap_err("Could not serialize worktree metadata")?;
+        fs::write(&path, bytes)
+            .wrap_err_with(|| format!("Could not write worktree metadata to {:?}", path))
+    }
+}
+
+fn key(repo: &str, worktree: &str) -> String {
+    format!("{}/{}", repo, worktree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ticket_id() {
+        assert_eq!(
+            ticket_id("https://acme.atlassian.net/browse/PROJ-123"),
+            "PROJ-123"
+        );
+        assert_eq!(ticket_id("https://github.com/owner/repo/issues/42/"), "#42");
+        assert_eq!(
+            ticket_id("https://linear.app/acme/issue/ENG-7/fix-login?tab=1"),
+            "ENG-7"
+        );
+    }
+
+    #[test]
+    fn test_pinned_metadata_is_kept() {
+        let pinned = WorktreeMetadata {
+            pinned: true,
+            ..Default::default()
+        };
+        let mut store = MetadataStore::default();
+        store.set("shanti", "release", pinned);
+        assert!(store.get("shanti", "release").is_some_and(|m| m.pinned));
+
+        store.set("shanti", "release", WorktreeMetadata::default());
+        assert!(store.get("shanti", "release").is_none());
+    }
+
+    #[test]
+    fn test_remove_orphans() {
+        let metadata = WorktreeMetadata {
+            tags: vec!["wip".to_string()],
+            ..Default::default()
+        };
+        let mut store = MetadataStore::default();
+        store.set("shanti", "live", metadata.clone());
+        store.set("shanti", "deleted", metadata.clone());
+        store.set("elsewhere", "unscanned", metadata);
+
+        store.remove_orphans(
+            &["shanti".to_string()],
+            &[("shanti".to_string(), "live".to_string())],
+        );
+
+        assert!(store.get("shanti", "live").is_some());
+        assert!(store.get("shanti", "deleted").is_none());
+        assert!(store.get("elsewhere", "unscanned").is_some());
+    }
+}
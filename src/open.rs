@@ -0,0 +1,136 @@
+use color_eyre::eyre::{self, eyre, WrapErr};
+use serde::Deserialize;
+use std::process::{Command, Stdio};
+
+/// The `[open]` section: commands that open a worktree without leaving
+/// shanti. Templates are split on whitespace and `{path}` is replaced by the
+/// worktree path; without `{path}` the path is passed as the last argument.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OpenConfig {
+    /// Editor command, e.g. `code {path}` or `nvim`. Falls back to `$VISUAL`,
+    /// then `$EDITOR`.
+    pub editor: Option<String>,
+    /// Whether the editor runs in shanti's terminal, which is handed over
+    /// until the editor exits. Defaults to on for `$VISUAL` / `$EDITOR`,
+    /// which are terminal editors by convention, and off for `editor`.
+    pub foreground: Option<bool>,
+    /// Command opening a new terminal window, e.g.
+    /// `wezterm start --cwd {path}`. Falls back to `$TERMINAL`. It runs in
+    /// the worktree, so terminals that open in their working directory need
+    /// no `{path}`.
+    pub terminal: Option<String>,
+}
+
+/// A command to start for a worktree.
+pub struct Launch {
+    pub command: Command,
+    /// Needs the terminal, so the TUI is suspended until it exits.
+    pub foreground: bool,
+}
+
+impl OpenConfig {
+    pub fn editor(&self, path: &str) -> eyre::Result<Launch> {
+        let (template, foreground) = match &self.editor {
+            Some(editor) => (editor.clone(), self.foreground.unwrap_or(false)),
+            None => {
+                let editor = std::env::var("VISUAL")
+                    .or_else(|_| std::env::var("EDITOR"))
+                    .map_err(|_| {
+                        eyre!("Set open.editor in the config, or $VISUAL / $EDITOR, to open worktrees")
+                    })?;
+                (editor, self.foreground.unwrap_or(true))
+            }
+        };
+        Ok(Launch {
+            command: command(&template, path, true)?,
+            foreground,
+        })
+    }
+
+    pub fn terminal(&self, path: &str) -> eyre::Result<Launch> {
+        let template = match &self.terminal {
+            Some(terminal) => terminal.clone(),
+            None => std::env::var("TERMINAL").map_err(|_| {
+                eyre!("Set open.terminal in the config, or $TERMINAL, to open terminals")
+            })?,
+        };
+        Ok(Launch {
+            command: command(&template, path, false)?,
+            foreground: false,
+        })
+    }
+}
+
+impl Launch {
+    /// Starts a command that doesn't need the terminal, detached from it.
+    pub fn spawn(mut self) -> eyre::Result<()> {
+        self.command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .wrap_err_with(|| format!("Could not run {:?}", self.command.get_program()))?;
+        Ok(())
+    }
+}
+
+/// Builds the command of `template` for `path`, running in `path`. Unless
+/// `append_path` is set, a template without `{path}` gets no path argument.
+fn command(template: &str, path: &str, append_path: bool) -> eyre::Result<Command> {
+    let mut words = template.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| eyre!("Command {:?} is empty", template))?;
+    let mut command = Command::new(program.replace("{path}", path));
+    command
+        .args(words.map(|word| word.replace("{path}", path)))
+        .current_dir(path);
+    if append_path && !template.contains("{path}") {
+        command.arg(path);
+    }
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_command_templates() {
+        let config = OpenConfig {
+            editor: Some("code --reuse-window {path}".to_string()),
+            foreground: None,
+            terminal: Some("gnome-terminal --working-directory={path}".to_string()),
+        };
+        let editor = config.editor("/wt/my app").unwrap();
+        assert_eq!(editor.command.get_program(), "code");
+        assert_eq!(args(&editor.command), ["--reuse-window", "/wt/my app"]);
+        assert!(!editor.foreground);
+
+        let terminal = config.terminal("/wt/api").unwrap();
+        assert_eq!(args(&terminal.command), ["--working-directory=/wt/api"]);
+
+        let config = OpenConfig {
+            editor: Some("nvim".to_string()),
+            foreground: Some(true),
+            terminal: Some("kitty".to_string()),
+        };
+        let editor = config.editor("/wt/api").unwrap();
+        assert_eq!(args(&editor.command), ["/wt/api"]);
+        assert!(editor.foreground);
+        let terminal = config.terminal("/wt/api").unwrap();
+        assert!(args(&terminal.command).is_empty());
+        assert_eq!(
+            terminal.command.get_current_dir(),
+            Some(std::path::Path::new("/wt/api"))
+        );
+    }
+}
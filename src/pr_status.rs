@@ -0,0 +1,159 @@
+use color_eyre::eyre::{self, WrapErr};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+use tracing::debug;
+
+use crate::{dirs, git, github};
+
+const CACHE_FILE: &str = "pr_status.json";
+
+/// Identifies the PR lookup for a single worktree.
+#[derive(Clone)]
+pub struct PrLookup {
+    pub worktree_path: String,
+    pub owner: String,
+    pub repo: String,
+    pub branch: String,
+}
+
+impl PrLookup {
+    /// The lookup for the branch checked out in `worktree`, which can differ
+    /// from the worktree's directory name. `None` when the repository has no
+    /// GitHub `origin`.
+    pub fn for_worktree(worktree: &git::Worktree) -> Option<Self> {
+        let (owner, repo) = github::parse_remote_url(&worktree.origin_url()?)?;
+        Some(Self {
+            worktree_path: worktree.path().to_string(),
+            owner,
+            repo,
+            branch: worktree.branch.clone(),
+        })
+    }
+
+    fn cache_key(&self) -> String {
+        format!("{}/{}:{}", self.owner, self.repo, self.branch)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    status: Option<github::PrStatus>,
+}
+
+/// PR statuses persisted in the data directory so the list has something to
+/// show before the background refresh completes.
+#[derive(Default)]
+pub struct PrStatusCache {
+    data_dir: Option<PathBuf>,
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl PrStatusCache {
+    /// Loads the statuses cached in `data_dir` by an earlier run.
+    pub fn load(data_dir: Option<&Path>) -> Self {
+        let entries = dirs::data_file(data_dir, CACHE_FILE)
+            .and_then(|path| fs::read(path).wrap_err("Could not read PR status cache"))
+            .and_then(|bytes| {
+                serde_json::from_slice(&bytes).wrap_err("Could not parse PR status cache")
+            })
+            .unwrap_or_else(|e| {
+                debug!("Starting with an empty PR status cache: {:#}", e);
+                HashMap::new()
+            });
+        Self {
+            data_dir: data_dir.map(Path::to_path_buf),
+            entries,
+        }
+    }
+
+    pub fn get(&self, lookup: &PrLookup) -> Option<&github::PrStatus> {
+        self.entries
+            .get(&lookup.cache_key())
+            .and_then(|entry| entry.status.as_ref())
+    }
+
+    pub fn insert(&mut self, lookup: &PrLookup, status: Option<github::PrStatus>) {
+        self.entries
+            .insert(lookup.cache_key(), CacheEntry { status });
+    }
+
+    pub fn save(&self) -> eyre::Result<()> {
+        let path = dirs::data_file(self.data_dir.as_deref(), CACHE_FILE)?;
+        let bytes =
+            serde_json::to_vec(&self.entries).wrap_err("Could not serialize PR status cache")?;
+        fs::write(&path, bytes)
+            .wrap_err_with(|| format!("Could not write PR status cache to {:?}", path))
+    }
+}
+
+/// Fetches the PR status of every lookup on a background thread. Results are
+/// sent as they arrive; lookups that fail are logged and skipped so the cached
+/// value stays in place. The channel disconnects once all lookups are done.
+pub fn spawn_refresh(
+    github: github::GithubClient,
+    lookups: Vec<PrLookup>,
+) -> Receiver<(PrLookup, Option<github::PrStatus>)> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        lookups
+            .into_par_iter()
+            .for_each_with(sender, |sender, lookup| {
+                let status = github.fetch_pr_status(&lookup.owner, &lookup.repo, &lookup.branch);
+                match status {
+                    Ok(status) => {
+                        let _ = sender.send((lookup, status));
+                    }
+                    Err(e) => debug!(
+                        "Could not fetch PR status for {}: {:#}",
+                        lookup.cache_key(),
+                        e
+                    ),
+                }
+            });
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_uses_the_branch() {
+        let repos_dir = tempfile::tempdir().unwrap();
+        let worktrees_dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(repos_dir.path().join("api")).unwrap();
+        repo.remote("origin", "git@github.com:acme/api.git")
+            .unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        let branch = repo
+            .branch("feat/login", &repo.find_commit(commit).unwrap(), false)
+            .unwrap();
+        let mut options = git2::WorktreeAddOptions::new();
+        options.reference(Some(branch.get()));
+        repo.worktree(
+            "feat-login",
+            &worktrees_dir.path().join("feat-login"),
+            Some(&options),
+        )
+        .unwrap();
+
+        let repositories = git::list_repositories(repos_dir.path().to_str().unwrap(), false);
+        let worktrees = git::worktrees_of_repositories(&repositories);
+        let lookup = PrLookup::for_worktree(&worktrees[0]).unwrap();
+        assert_eq!(lookup.cache_key(), "acme/api:feat/login");
+    }
+}
@@ -0,0 +1,132 @@
+use color_eyre::eyre::{self, WrapErr};
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    process::{Command, Stdio},
+};
+
+/// The `[sessions]` section: one terminal multiplexer session per worktree.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SessionsConfig {
+    /// Selecting a worktree opens its session instead of exiting with its
+    /// path. Off when unset.
+    pub multiplexer: Option<Multiplexer>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Multiplexer {
+    Tmux,
+    Zellij,
+}
+
+impl Multiplexer {
+    /// The session of a worktree, `repo/branch` as far as the multiplexer
+    /// allows: tmux takes `.` and `:` as separators, and zellij names its
+    /// socket after the session.
+    pub fn session_name(self, repo: &str, branch: &str) -> String {
+        let name = format!("{}/{}", repo, branch);
+        match self {
+            Multiplexer::Tmux => name.replace(['.', ':'], "_"),
+            Multiplexer::Zellij => name.replace('/', "-"),
+        }
+    }
+
+    /// Names of the running sessions; none when the multiplexer isn't
+    /// running or installed.
+    pub fn live_sessions(self) -> HashSet<String> {
+        let output = match self {
+            Multiplexer::Tmux => Command::new("tmux")
+                .args(["list-sessions", "-F", "#{session_name}"])
+                .stderr(Stdio::null())
+                .output(),
+            Multiplexer::Zellij => Command::new("zellij")
+                .args(["list-sessions", "--short", "--no-formatting"])
+                .stderr(Stdio::null())
+                .output(),
+        };
+        match output {
+            Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            _ => HashSet::new(),
+        }
+    }
+
+    /// Creates the session `name` in `path` unless it is running, and switches
+    /// to it. Returns the command to run in the foreground when attaching
+    /// needs the terminal, i.e. when shanti doesn't run inside the
+    /// multiplexer.
+    pub fn open(self, name: &str, path: &str) -> eyre::Result<Option<Command>> {
+        match self {
+            Multiplexer::Tmux => {
+                let target = format!("={}", name);
+                let exists = quiet("tmux", &["has-session", "-t", &target]).is_ok();
+                if !exists {
+                    quiet("tmux", &["new-session", "-d", "-s", name, "-c", path])?;
+                }
+                if std::env::var_os("TMUX").is_some() {
+                    quiet("tmux", &["switch-client", "-t", &target])?;
+                    return Ok(None);
+                }
+                let mut attach = Command::new("tmux");
+                attach.args(["attach-session", "-t", &target]);
+                Ok(Some(attach))
+            }
+            Multiplexer::Zellij => {
+                if std::env::var_os("ZELLIJ").is_some() {
+                    eyre::bail!("zellij can't switch sessions from inside a session — detach and run shanti outside zellij");
+                }
+                let mut attach = Command::new("zellij");
+                attach.args(["attach", "--create", name]).current_dir(path);
+                Ok(Some(attach))
+            }
+        }
+    }
+
+    /// Ends the session `name`, if it is running.
+    pub fn kill(self, name: &str) -> eyre::Result<()> {
+        if !self.live_sessions().contains(name) {
+            return Ok(());
+        }
+        match self {
+            Multiplexer::Tmux => quiet("tmux", &["kill-session", "-t", &format!("={}", name)]),
+            Multiplexer::Zellij => quiet("zellij", &["delete-session", "--force", name]),
+        }
+    }
+}
+
+/// Runs `program` without output, failing when it exits unsuccessfully.
+fn quiet(program: &str, args: &[&str]) -> eyre::Result<()> {
+    let status = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .wrap_err_with(|| format!("Could not run {}", program))?;
+    if !status.success() {
+        eyre::bail!("`{} {}` failed with {}", program, args.join(" "), status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_name() {
+        assert_eq!(
+            Multiplexer::Tmux.session_name("web.app", "feat/login"),
+            "web_app/feat/login"
+        );
+        assert_eq!(
+            Multiplexer::Zellij.session_name("api", "feat/login"),
+            "api-feat-login"
+        );
+    }
+}
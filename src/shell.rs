@@ -0,0 +1,174 @@
+use clap::CommandFactory;
+
+use crate::cli::Args;
+
+/// Shells `shanti init` writes a wrapper function for.
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Nushell,
+}
+
+/// A function named `shanti` that runs the binary and changes to the selected
+/// worktree. It stays put when shanti is cancelled (exit code 1), returns the
+/// exit code of errors, and runs subcommands and `--help` / `--version` as
+/// they are. With `bind`, Ctrl+`bind` opens shanti from the prompt.
+pub fn init_script(shell: Shell, bind: Option<char>) -> String {
+    let mut passthrough: Vec<String> = Args::command()
+        .get_subcommands()
+        .map(|command| command.get_name().to_string())
+        .collect();
+    passthrough.extend(["help", "-h", "--help", "-V", "--version"].map(String::from));
+
+    let mut script = match shell {
+        Shell::Bash | Shell::Zsh => posix_function(&passthrough.join("|")),
+        Shell::Fish => fish_function(&passthrough.join(" ")),
+        Shell::Nushell => nushell_function(&passthrough.join(" ")),
+    };
+    if let Some(key) = bind {
+        script.push_str(&match shell {
+            Shell::Bash => format!(
+                "\nif [[ $- == *i* ]]; then\n    bind -x '\"\\C-{}\": shanti'\nfi\n",
+                key
+            ),
+            Shell::Zsh => format!(
+                r#"
+_shanti_widget() {{
+    shanti </dev/tty
+    zle reset-prompt
+}}
+zle -N _shanti_widget
+bindkey '^{}' _shanti_widget
+"#,
+                key.to_ascii_uppercase()
+            ),
+            Shell::Fish => format!("\nbind \\c{} 'shanti; commandline -f repaint'\n", key),
+            Shell::Nushell => format!(
+                r#"
+$env.config.keybindings = ($env.config.keybindings | append {{
+    name: shanti
+    modifier: control
+    keycode: char_{}
+    mode: [emacs vi_normal vi_insert]
+    event: {{ send: executehostcommand, cmd: "shanti" }}
+}})
+"#,
+                key
+            ),
+        });
+    }
+    script
+}
+
+fn posix_function(passthrough: &str) -> String {
+    format!(
+        r#"shanti() {{
+    case "${{1:-}}" in
+        {passthrough})
+            command shanti "$@"
+            return
+            ;;
+    esac
+    local file dir code
+    file="$(mktemp)" || return
+    command shanti --output-file "$file" "$@"
+    code=$?
+    dir="$(cat "$file")"
+    rm -f "$file"
+    case $code in
+        0) [ -n "$dir" ] && builtin cd -- "$dir" ;;
+        1) return 0 ;;
+        *) return $code ;;
+    esac
+}}
+"#
+    )
+}
+
+fn fish_function(passthrough: &str) -> String {
+    format!(
+        r#"function shanti
+    switch "$argv[1]"
+        case {passthrough}
+            command shanti $argv
+            return
+    end
+    set -l file (mktemp); or return
+    command shanti --output-file $file $argv
+    set -l code $status
+    set -l dir (cat $file)
+    rm -f $file
+    switch $code
+        case 0
+            test -n "$dir"; and cd $dir
+        case 1
+            return 0
+        case '*'
+            return $code
+    end
+end
+"#
+    )
+}
+
+fn nushell_function(passthrough: &str) -> String {
+    format!(
+        r#"def --env --wrapped shanti [...args: string] {{
+    if ($args | is-not-empty) and ($args | first) in [{passthrough}] {{
+        ^shanti ...$args
+        return
+    }}
+    let file = (mktemp -t)
+    let code = try {{ ^shanti --output-file $file ...$args; 0 }} catch {{ $env.LAST_EXIT_CODE }}
+    let dir = (open --raw $file | str trim)
+    rm -f $file
+    # Cancelling exits with 1; shanti already reported errors, which exit
+    # with 2 or more.
+    if $code >= 2 {{
+        error make --unspanned {{ msg: $"shanti exited with ($code)" }}
+    }}
+    if ($dir | is-not-empty) {{
+        cd $dir
+    }}
+}}
+"#
+    )
+}
+
+/// Parses the `--bind` key: a letter, bound with Ctrl.
+pub fn parse_key(value: &str) -> Result<char, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(key), None) if key.is_ascii_alphabetic() => Ok(key.to_ascii_lowercase()),
+        _ => Err(format!("{:?} is not a single letter", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_script() {
+        let bash = init_script(Shell::Bash, None);
+        assert!(
+            bash.contains("init|completions|man|exec|help|-h|--help|-V|--version)"),
+            "{}",
+            bash
+        );
+        assert!(!bash.contains("bind"));
+
+        let zsh = init_script(Shell::Zsh, Some('g'));
+        assert!(zsh.contains("bindkey '^G' _shanti_widget"), "{}", zsh);
+        let fish = init_script(Shell::Fish, Some('o'));
+        assert!(fish.contains("bind \\co 'shanti; commandline -f repaint'"));
+        let nushell = init_script(Shell::Nushell, Some('g'));
+        assert!(nushell.contains("keycode: char_g"));
+        assert!(nushell.contains("if $code >= 2"), "{}", nushell);
+
+        assert_eq!(parse_key("G"), Ok('g'));
+        assert!(parse_key("ctrl-g").is_err());
+    }
+}
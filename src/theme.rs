@@ -0,0 +1,361 @@
+use color_eyre::eyre::{self, eyre};
+use ratatui::style::{
+    palette::tailwind::{AMBER, BLUE, GREEN, RED, SLATE, VIOLET},
+    Color, Modifier, Style,
+};
+use serde::Deserialize;
+use std::{str::FromStr, sync::RwLock};
+
+/// The `[theme]` section of the config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub preset: Preset,
+    /// Status glyphs in plain ASCII, for terminals without good Unicode fonts.
+    pub ascii: bool,
+    /// Colours replacing those of the preset, e.g. `accent = "#50fa7b"`.
+    pub colors: ColorOverrides,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+/// Any colour ratatui can parse: a name such as `lightgreen`, `#rrggbb` or an
+/// ANSI index.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorOverrides {
+    pub accent: Option<String>,
+    pub accent_bright: Option<String>,
+    pub danger: Option<String>,
+    pub danger_soft: Option<String>,
+    pub danger_bg: Option<String>,
+    pub warning: Option<String>,
+    pub warning_soft: Option<String>,
+    pub info: Option<String>,
+    pub info_soft: Option<String>,
+    pub special: Option<String>,
+    pub text: Option<String>,
+    pub text_muted: Option<String>,
+    pub text_dim: Option<String>,
+    pub hint: Option<String>,
+    pub faint: Option<String>,
+    pub selected_bg: Option<String>,
+    pub border: Option<String>,
+}
+
+/// Symbols shown for the state of worktrees and results.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Glyphs {
+    pub pushed: &'static str,
+    pub gone: &'static str,
+    pub never_pushed: &'static str,
+    pub dirty: &'static str,
+    pub pinned: &'static str,
+    pub marked: &'static str,
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    pub success: &'static str,
+    pub failure: &'static str,
+    pub pending: &'static str,
+    pub warning: &'static str,
+    pub session: &'static str,
+}
+
+const UNICODE_GLYPHS: Glyphs = Glyphs {
+    pushed: "✔",
+    gone: "✘",
+    never_pushed: "⬆",
+    dirty: "*",
+    pinned: "★",
+    marked: "●",
+    expanded: "▾",
+    collapsed: "▸",
+    success: "✔",
+    failure: "✘",
+    pending: "●",
+    warning: "⚠",
+    session: "▣",
+};
+
+const ASCII_GLYPHS: Glyphs = Glyphs {
+    pushed: "=",
+    gone: "x",
+    never_pushed: "^",
+    dirty: "*",
+    pinned: "#",
+    marked: "+",
+    expanded: "v",
+    collapsed: ">",
+    success: "=",
+    failure: "x",
+    pending: "o",
+    warning: "!",
+    session: "@",
+};
+
+/// Colours by role rather than by hue, so presets can swap them wholesale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// Titles, key hints, popup borders and success states.
+    pub accent: Color,
+    /// Focused inputs and popup titles.
+    pub accent_bright: Color,
+    pub danger: Color,
+    pub danger_soft: Color,
+    pub danger_bg: Color,
+    pub warning: Color,
+    pub warning_soft: Color,
+    pub info: Color,
+    pub info_soft: Color,
+    /// Merged PRs, visual mode and other states that are neither good nor bad.
+    pub special: Color,
+    pub text: Color,
+    pub text_muted: Color,
+    pub text_dim: Color,
+    /// Descriptions next to key hints.
+    pub hint: Color,
+    /// Separators, scrollbars and other chrome.
+    pub faint: Color,
+    pub selected_bg: Color,
+    pub border: Color,
+    pub glyphs: Glyphs,
+    /// Set by `NO_COLOR`: every colour is the terminal default, and the
+    /// selection is shown in reverse video instead.
+    no_color: bool,
+}
+
+static CURRENT: RwLock<Theme> = RwLock::new(Theme::dark());
+
+/// The theme the TUI is drawn with.
+pub fn current() -> Theme {
+    *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn set(theme: Theme) {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+impl Theme {
+    pub const fn dark() -> Self {
+        Self {
+            accent: GREEN.c400,
+            accent_bright: GREEN.c300,
+            danger: RED.c400,
+            danger_soft: RED.c300,
+            danger_bg: RED.c950,
+            warning: AMBER.c400,
+            warning_soft: AMBER.c300,
+            info: BLUE.c400,
+            info_soft: BLUE.c300,
+            special: VIOLET.c300,
+            text: Color::White,
+            text_muted: SLATE.c300,
+            text_dim: SLATE.c400,
+            hint: SLATE.c500,
+            faint: SLATE.c700,
+            selected_bg: BLUE.c800,
+            border: SLATE.c500,
+            glyphs: UNICODE_GLYPHS,
+            no_color: false,
+        }
+    }
+
+    /// Darker shades that stay readable on a light background.
+    pub const fn light() -> Self {
+        Self {
+            accent: GREEN.c700,
+            accent_bright: GREEN.c600,
+            danger: RED.c600,
+            danger_soft: RED.c700,
+            danger_bg: RED.c100,
+            warning: AMBER.c700,
+            warning_soft: AMBER.c600,
+            info: BLUE.c700,
+            info_soft: BLUE.c600,
+            special: VIOLET.c700,
+            text: SLATE.c900,
+            text_muted: SLATE.c700,
+            text_dim: SLATE.c600,
+            hint: SLATE.c500,
+            faint: SLATE.c300,
+            selected_bg: BLUE.c200,
+            border: SLATE.c400,
+            glyphs: UNICODE_GLYPHS,
+            no_color: false,
+        }
+    }
+
+    /// The 16 ANSI colours, which terminals tune for contrast with their own
+    /// background.
+    pub const fn high_contrast() -> Self {
+        Self {
+            accent: Color::LightGreen,
+            accent_bright: Color::LightGreen,
+            danger: Color::LightRed,
+            danger_soft: Color::LightRed,
+            danger_bg: Color::Reset,
+            warning: Color::LightYellow,
+            warning_soft: Color::LightYellow,
+            info: Color::LightCyan,
+            info_soft: Color::LightCyan,
+            special: Color::LightMagenta,
+            text: Color::Reset,
+            text_muted: Color::Reset,
+            text_dim: Color::Reset,
+            hint: Color::Reset,
+            faint: Color::Reset,
+            selected_bg: Color::Blue,
+            border: Color::Reset,
+            glyphs: UNICODE_GLYPHS,
+            no_color: false,
+        }
+    }
+
+    fn no_color(glyphs: Glyphs) -> Self {
+        Self {
+            accent: Color::Reset,
+            accent_bright: Color::Reset,
+            danger: Color::Reset,
+            danger_soft: Color::Reset,
+            danger_bg: Color::Reset,
+            warning: Color::Reset,
+            warning_soft: Color::Reset,
+            info: Color::Reset,
+            info_soft: Color::Reset,
+            special: Color::Reset,
+            text: Color::Reset,
+            text_muted: Color::Reset,
+            text_dim: Color::Reset,
+            hint: Color::Reset,
+            faint: Color::Reset,
+            selected_bg: Color::Reset,
+            border: Color::Reset,
+            glyphs,
+            no_color: true,
+        }
+    }
+
+    /// The theme described by the config. With `no_color` set, colours from
+    /// the preset and overrides are ignored, but ASCII glyphs still apply.
+    pub fn from_config(config: &ThemeConfig, no_color: bool) -> eyre::Result<Self> {
+        let glyphs = if config.ascii {
+            ASCII_GLYPHS
+        } else {
+            UNICODE_GLYPHS
+        };
+        if no_color {
+            return Ok(Self::no_color(glyphs));
+        }
+        let mut theme = match config.preset {
+            Preset::Dark => Self::dark(),
+            Preset::Light => Self::light(),
+            Preset::HighContrast => Self::high_contrast(),
+        };
+        theme.glyphs = glyphs;
+
+        let colors = &config.colors;
+        for (name, value, color) in [
+            ("accent", &colors.accent, &mut theme.accent),
+            (
+                "accent_bright",
+                &colors.accent_bright,
+                &mut theme.accent_bright,
+            ),
+            ("danger", &colors.danger, &mut theme.danger),
+            ("danger_soft", &colors.danger_soft, &mut theme.danger_soft),
+            ("danger_bg", &colors.danger_bg, &mut theme.danger_bg),
+            ("warning", &colors.warning, &mut theme.warning),
+            (
+                "warning_soft",
+                &colors.warning_soft,
+                &mut theme.warning_soft,
+            ),
+            ("info", &colors.info, &mut theme.info),
+            ("info_soft", &colors.info_soft, &mut theme.info_soft),
+            ("special", &colors.special, &mut theme.special),
+            ("text", &colors.text, &mut theme.text),
+            ("text_muted", &colors.text_muted, &mut theme.text_muted),
+            ("text_dim", &colors.text_dim, &mut theme.text_dim),
+            ("hint", &colors.hint, &mut theme.hint),
+            ("faint", &colors.faint, &mut theme.faint),
+            ("selected_bg", &colors.selected_bg, &mut theme.selected_bg),
+            ("border", &colors.border, &mut theme.border),
+        ] {
+            if let Some(value) = value {
+                *color = Color::from_str(value)
+                    .map_err(|_| eyre!("Invalid colour {:?} for theme.colors.{}", value, name))?;
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Selected list row: blue bg matching lazygit's selectedLineBgColor.
+    pub fn selected(&self) -> Style {
+        if self.no_color {
+            Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            Style::new()
+                .bg(self.selected_bg)
+                .add_modifier(Modifier::BOLD)
+        }
+    }
+
+    /// Muted border for the main panel — lazygit inactiveBorderColor is terminal default.
+    pub fn border(&self) -> Style {
+        Style::new().fg(self.border)
+    }
+
+    /// Bold border for popups — lazygit activeBorderColor is [green, bold].
+    pub fn popup_border(&self) -> Style {
+        Style::new().fg(self.accent).add_modifier(Modifier::BOLD)
+    }
+
+    /// Border for focused inputs — one step brighter than the popup border.
+    pub fn active_border(&self) -> Style {
+        Style::new()
+            .fg(self.accent_bright)
+            .add_modifier(Modifier::BOLD)
+    }
+}
+
+/// Whether `NO_COLOR` asks for output without colours, see https://no-color.org.
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_config() {
+        let config: ThemeConfig = toml::from_str(
+            r##"
+            preset = "light"
+            ascii = true
+            colors = { accent = "#102030", hint = "red" }
+            "##,
+        )
+        .unwrap();
+        let theme = Theme::from_config(&config, false).unwrap();
+        assert_eq!(theme.accent, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(theme.hint, Color::Red);
+        assert_eq!(theme.text, Theme::light().text);
+        assert_eq!(theme.glyphs.pushed, "=");
+
+        let theme = Theme::from_config(&config, true).unwrap();
+        assert_eq!(theme.accent, Color::Reset);
+        assert!(theme.selected().add_modifier.contains(Modifier::REVERSED));
+        assert_eq!(theme.glyphs.pushed, "=");
+
+        let config: ThemeConfig = toml::from_str(r#"colors = { text = "not-a-colour" }"#).unwrap();
+        assert!(Theme::from_config(&config, false).is_err());
+    }
+}
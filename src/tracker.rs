@@ -0,0 +1,239 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use color_eyre::eyre::{self, WrapErr};
+use serde::Deserialize;
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+const DEFAULT_BRANCH_TEMPLATE: &str = "{key}-{slug}";
+/// Longest slug put in a branch name, so long issue titles stay readable.
+const MAX_SLUG_LEN: usize = 40;
+
+const LINEAR_ISSUE_QUERY: &str = "\
+query($id: String!) {
+  issue(id: $id) { identifier title url }
+}";
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackerKind {
+    Jira,
+    Linear,
+}
+
+/// The `[tracker]` section of the config file.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TrackerConfig {
+    pub kind: TrackerKind,
+    /// Base URL of the JIRA site, e.g. `https://acme.atlassian.net`. Defaults
+    /// to the public API for Linear.
+    pub url: Option<String>,
+    /// Branch name proposed for an issue, with `{key}` and `{slug}` placeholders.
+    #[serde(default = "default_branch_template")]
+    pub branch_template: String,
+}
+
+fn default_branch_template() -> String {
+    DEFAULT_BRANCH_TEMPLATE.to_string()
+}
+
+#[derive(Clone, Debug)]
+pub struct Issue {
+    pub key: String,
+    pub title: String,
+    pub url: String,
+}
+
+/// Extracts the issue key from what the user typed: the key itself
+/// (`PROJ-1234`) or a link to the issue, e.g.
+/// `https://acme.atlassian.net/browse/PROJ-1234` or
+/// `https://linear.app/acme/issue/ENG-7/fix-login`.
+pub fn parse_issue_key(input: &str) -> eyre::Result<String> {
+    let input = input.trim();
+    let candidates: Vec<&str> = if input.contains("://") {
+        input.split(['/', '?', '&', '=', '#']).collect()
+    } else {
+        vec![input]
+    };
+    candidates
+        .into_iter()
+        .find(|candidate| is_issue_key(candidate))
+        .map(|key| key.to_uppercase())
+        .ok_or_else(|| eyre::eyre!("Not an issue key or link — expected e.g. PROJ-1234"))
+}
+
+fn is_issue_key(candidate: &str) -> bool {
+    let Some((project, number)) = candidate.rsplit_once('-') else {
+        return false;
+    };
+    project.starts_with(|c: char| c.is_ascii_alphabetic())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Fetches the issue `key` from the configured tracker. The token is read from
+/// `JIRA_API_TOKEN` (with `JIRA_EMAIL` for JIRA Cloud) or `LINEAR_API_KEY`.
+pub fn fetch_issue(config: &TrackerConfig, key: &str) -> eyre::Result<Issue> {
+    match config.kind {
+        TrackerKind::Jira => fetch_jira_issue(config, key),
+        TrackerKind::Linear => fetch_linear_issue(config, key),
+    }
+}
+
+fn fetch_jira_issue(config: &TrackerConfig, key: &str) -> eyre::Result<Issue> {
+    let base_url = config
+        .url
+        .as_deref()
+        .ok_or_else(|| eyre::eyre!("Set `url` in the [tracker] section to your JIRA site"))?
+        .trim_end_matches('/');
+    let token = std::env::var("JIRA_API_TOKEN")
+        .map_err(|_| eyre::eyre!("JIRA_API_TOKEN not set — create an API token in JIRA"))?;
+    // JIRA Cloud authenticates API tokens with the account email, Data Center
+    // takes personal access tokens as bearer tokens.
+    let authorization = match std::env::var("JIRA_EMAIL") {
+        Ok(email) => format!(
+            "Basic {}",
+            BASE64_STANDARD.encode(format!("{}:{}", email, token))
+        ),
+        Err(_) => format!("Bearer {}", token),
+    };
+
+    let response = ureq::get(&format!(
+        "{}/rest/api/2/issue/{}?fields=summary",
+        base_url, key
+    ))
+    .config()
+    .http_status_as_error(false)
+    .build()
+    .header("Authorization", &authorization)
+    .header("Accept", "application/json")
+    .call()
+    .wrap_err("Could not reach JIRA")?;
+    let json = read_json(response, key, "JIRA_API_TOKEN")?;
+
+    let title = json["fields"]["summary"]
+        .as_str()
+        .ok_or_else(|| eyre::eyre!("Unexpected JIRA response: missing fields.summary"))?;
+    // The key JIRA answers with, as an issue moved to another project is
+    // still found by its old key.
+    let key = json["key"].as_str().unwrap_or(key);
+    Ok(Issue {
+        key: key.to_string(),
+        title: title.to_string(),
+        url: format!("{}/browse/{}", base_url, key),
+    })
+}
+
+fn fetch_linear_issue(config: &TrackerConfig, key: &str) -> eyre::Result<Issue> {
+    let url = config.url.as_deref().unwrap_or(LINEAR_API_URL);
+    let token = std::env::var("LINEAR_API_KEY")
+        .map_err(|_| eyre::eyre!("LINEAR_API_KEY not set — create a personal API key in Linear"))?;
+
+    let response = ureq::post(url)
+        .config()
+        .http_status_as_error(false)
+        .build()
+        .header("Authorization", &token)
+        .send_json(serde_json::json!({
+            "query": LINEAR_ISSUE_QUERY,
+            "variables": { "id": key },
+        }))
+        .wrap_err("Could not reach Linear")?;
+    let json = read_json(response, key, "LINEAR_API_KEY")?;
+
+    if let Some(msg) = json["errors"][0]["message"].as_str() {
+        eyre::bail!("Linear API error: {}", msg);
+    }
+    let issue = &json["data"]["issue"];
+    let title = issue["title"]
+        .as_str()
+        .ok_or_else(|| eyre::eyre!("Issue {} not found", key))?;
+    Ok(Issue {
+        key: issue["identifier"].as_str().unwrap_or(key).to_string(),
+        title: title.to_string(),
+        url: issue["url"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+fn read_json(
+    response: ureq::http::Response<ureq::Body>,
+    key: &str,
+    token_var: &str,
+) -> eyre::Result<serde_json::Value> {
+    match response.status().as_u16() {
+        401 | 403 => eyre::bail!("Tracker auth failed — check {}", token_var),
+        404 => eyre::bail!("Issue {} not found", key),
+        status if status >= 400 => eyre::bail!("Tracker API error: HTTP {}", status),
+        _ => {}
+    }
+    let bytes = response
+        .into_body()
+        .read_to_vec()
+        .wrap_err("Failed to read tracker response")?;
+    serde_json::from_slice(&bytes).wrap_err("Failed to parse tracker response")
+}
+
+/// Fills the `{key}` and `{slug}` placeholders of `template` for `issue`.
+pub fn branch_name(template: &str, issue: &Issue) -> String {
+    template
+        .replace("{key}", &issue.key)
+        .replace("{slug}", &slugify(&issue.title))
+}
+
+/// Lowercase, dash-separated form of an issue title, cut at a word boundary
+/// after at most `MAX_SLUG_LEN` characters.
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if !slug.is_empty() && slug.len() + 1 + word.len() > MAX_SLUG_LEN {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(word);
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_issue_key() {
+        for input in [
+            "PROJ-1234",
+            "proj-1234",
+            " PROJ-1234 ",
+            "https://acme.atlassian.net/browse/PROJ-1234",
+            "https://acme.atlassian.net/jira/software/projects/PROJ/boards/1?selectedIssue=PROJ-1234",
+            "https://linear.app/acme/issue/PROJ-1234/fix-the-login-page",
+        ] {
+            assert_eq!(parse_issue_key(input).unwrap(), "PROJ-1234", "{}", input);
+        }
+        assert!(parse_issue_key("fix-login").is_err());
+        assert!(parse_issue_key("1234").is_err());
+    }
+
+    #[test]
+    fn test_branch_name() {
+        let issue = Issue {
+            key: "PROJ-1234".to_string(),
+            title: "Fix: login fails when the password contains a \"quote\" character".to_string(),
+            url: String::new(),
+        };
+        assert_eq!(
+            branch_name("{key}-{slug}", &issue),
+            "PROJ-1234-fix-login-fails-when-the-password"
+        );
+        assert_eq!(branch_name("feat/{key}", &issue), "feat/PROJ-1234");
+    }
+}
@@ -0,0 +1,213 @@
+use std::{io::Write, process::Stdio};
+
+use assert_cmd::Command;
+
+#[test]
+fn test_init_needs_no_directories() {
+    let output = Command::cargo_bin("shanti")
+        .unwrap()
+        .env_remove("SHANTI_WORKTREES_DIR")
+        .args(["init", "bash", "--bind"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let script = String::from_utf8(output).unwrap();
+    assert!(script.contains("builtin cd -- \"$dir\""), "{}", script);
+    assert!(script.contains(r#"bind -x '"\C-g": shanti'"#), "{}", script);
+
+    // The script is valid bash.
+    let mut bash = std::process::Command::new("bash")
+        .arg("-n")
+        .stdin(Stdio::piped())
+        .spawn()
+        .unwrap();
+    bash.stdin
+        .take()
+        .unwrap()
+        .write_all(script.as_bytes())
+        .unwrap();
+    assert!(bash.wait().unwrap().success());
+}
+
+/// Runs `script` with `shell -c`, where `shanti` is the binary under test.
+fn run_shell(shell: &str, script: &str) -> std::process::Output {
+    let bin = assert_cmd::cargo::cargo_bin("shanti");
+    let path = std::env::join_paths(std::iter::once(bin.parent().unwrap().to_path_buf()).chain(
+        std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+    ))
+    .unwrap();
+    std::process::Command::new(shell)
+        .args(["-c", script])
+        .env("PATH", path)
+        .env_remove("SHANTI_WORKTREES_DIR")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_shell_wrappers_pass_on_errors() {
+    let output = run_shell(
+        "bash",
+        r#"eval "$(shanti init bash)"
+        shanti --worktrees-dir /nonexistent/shanti-worktrees
+        echo "exit $?""#,
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "exit 2\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Could not resolve the worktrees dir"),
+        "{}",
+        stderr
+    );
+
+    // nushell is only checked where it is installed.
+    if std::process::Command::new("nu")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let init = dir.path().join("init.nu");
+    std::fs::write(&init, run_shell("bash", "shanti init nushell").stdout).unwrap();
+    let output = run_shell(
+        "nu",
+        &format!(
+            "source {:?}; shanti --worktrees-dir /nonexistent/shanti-worktrees",
+            init
+        ),
+    );
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("shanti exited with 2"), "{}", stderr);
+}
+
+#[test]
+fn test_init_rejects_unknown_shells_and_keys() {
+    Command::cargo_bin("shanti")
+        .unwrap()
+        .args(["init", "tcsh"])
+        .assert()
+        .failure();
+    Command::cargo_bin("shanti")
+        .unwrap()
+        .args(["init", "zsh", "--bind", "ctrl-g"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_errors_exit_with_2() {
+    let assert = Command::cargo_bin("shanti")
+        .unwrap()
+        .args(["--worktrees-dir", "/nonexistent/shanti-worktrees"])
+        .assert()
+        .code(2)
+        .stdout("");
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).to_string();
+    assert!(
+        stderr.starts_with("shanti: Could not resolve the worktrees dir"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_completions() {
+    let output = Command::cargo_bin("shanti")
+        .unwrap()
+        .env_remove("SHANTI_WORKTREES_DIR")
+        .args(["completions", "bash"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let script = String::from_utf8(output).unwrap();
+    assert!(
+        script.contains("complete -o nospace -o bashdefault"),
+        "{}",
+        script
+    );
+
+    // The script calls shanti back with COMPLETE set for the candidates.
+    let output = Command::cargo_bin("shanti")
+        .unwrap()
+        .env("COMPLETE", "fish")
+        .args(["--", "shanti", "comp"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let candidates = String::from_utf8(output).unwrap();
+    assert!(candidates.starts_with("completions\t"), "{}", candidates);
+}
+
+#[test]
+fn test_man_page() {
+    let output = Command::cargo_bin("shanti")
+        .unwrap()
+        .env_remove("SHANTI_WORKTREES_DIR")
+        .arg("man")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let page = String::from_utf8(output).unwrap();
+    assert!(page.contains(".TH shanti 1"), "{}", page);
+    assert!(page.contains("worktrees\\-dir"), "{}", page);
+}
+
+#[test]
+fn test_exec_runs_in_matching_worktrees() {
+    let repos_dir = tempfile::tempdir().unwrap();
+    let worktrees_dir = tempfile::tempdir().unwrap();
+    for name in ["alpha", "beta"] {
+        let repo = git2::Repository::init(repos_dir.path().join(name)).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+            .unwrap();
+        let branch = repo
+            .branch("login", &repo.find_commit(commit).unwrap(), false)
+            .unwrap();
+        let mut options = git2::WorktreeAddOptions::new();
+        options.reference(Some(branch.get()));
+        repo.worktree(
+            "login",
+            &worktrees_dir.path().join(format!("{}-login", name)),
+            Some(&options),
+        )
+        .unwrap();
+    }
+
+    let assert = Command::cargo_bin("shanti")
+        .unwrap()
+        .env("SHANTI_DATA", worktrees_dir.path())
+        .args(["exec", "--repos-dir", repos_dir.path().to_str().unwrap()])
+        .args(["--repo", "alpha", "--status", "unpushed", "--"])
+        .args(["sh", "-c", "echo in $SHANTI_REPO; exit 3"])
+        .assert()
+        .code(3);
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).to_string();
+    assert!(stdout.contains("alpha/login | in alpha\n"), "{}", stdout);
+    assert!(!stdout.contains("beta"), "{}", stdout);
+    assert!(stdout.ends_with("0 passed, 1 failed\n"), "{}", stdout);
+
+    Command::cargo_bin("shanti")
+        .unwrap()
+        .env("SHANTI_DATA", worktrees_dir.path())
+        .env("SHANTI_REPOS_DIR", repos_dir.path())
+        .args(["exec", "--filter", "beta login", "--", "true"])
+        .assert()
+        .success()
+        .stdout("\npassed  beta/login\n1 passed, 0 failed\n");
+}
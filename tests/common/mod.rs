@@ -0,0 +1,121 @@
+// Each integration test binary uses a different subset of these helpers.
+#![allow(dead_code)]
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+pub struct StubResponse {
+    pub status: u16,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+impl StubResponse {
+    pub fn json(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: body.to_string(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+}
+
+/// Minimal HTTP server standing in for the GitHub and tracker APIs. Routes are
+/// matched on `"<METHOD> <path>"`; anything else gets GitHub's 404 body.
+pub struct StubServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl StubServer {
+    pub fn start(routes: Vec<(&'static str, StubResponse)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind the stub server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(&stream);
+                let mut head = String::new();
+                let mut content_length = 0;
+                let mut chunked = false;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                    if line
+                        .to_lowercase()
+                        .starts_with("transfer-encoding: chunked")
+                    {
+                        chunked = true;
+                    }
+                    head.push_str(&line);
+                }
+                let mut body = vec![0; content_length];
+                let _ = reader.read_exact(&mut body);
+                while chunked {
+                    let mut size = String::new();
+                    let _ = reader.read_line(&mut size);
+                    let size = usize::from_str_radix(size.trim(), 16).unwrap_or(0);
+                    let mut chunk = vec![0; size + 2];
+                    let _ = reader.read_exact(&mut chunk);
+                    body.extend_from_slice(&chunk[..size]);
+                    chunked = size > 0;
+                }
+                let request = format!("{}\r\n{}", head, String::from_utf8_lossy(&body));
+
+                let route = head.split(" HTTP/").next().unwrap_or_default().to_string();
+                recorded.lock().unwrap().push(request);
+
+                let not_found = StubResponse::json(404, r#"{"message": "Not Found"}"#);
+                let response = routes
+                    .iter()
+                    .find(|(r, _)| *r == route)
+                    .map(|(_, response)| response)
+                    .unwrap_or(&not_found);
+
+                let mut raw = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+                    response.status,
+                    response.body.len()
+                );
+                for (name, value) in &response.headers {
+                    raw.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                raw.push_str("\r\n");
+                raw.push_str(&response.body);
+                let _ = (&stream).write_all(raw.as_bytes());
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Raw text (request line, headers and body) of every request received so far.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// JSON body of the `index`th request.
+    pub fn request_json(&self, index: usize) -> serde_json::Value {
+        let request = &self.requests()[index];
+        let (_, body) = request
+            .split_once("\r\n\r\n")
+            .expect("Expected the request to have a body");
+        serde_json::from_str(body).expect("Expected the request body to be JSON")
+    }
+}
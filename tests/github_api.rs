@@ -0,0 +1,230 @@
+mod common;
+
+use common::{StubResponse, StubServer};
+use shanti::github::{self, CiStatus, GithubClient, GithubError, NewPullRequest, PrState, PrUrl};
+
+/// A `gh` that cannot be found, so every request takes the `GITHUB_TOKEN` fallback.
+const MISSING_GH: &str = "shanti-test-missing-gh";
+
+const PR_JSON: &str = r#"{"number": 59, "head": {"ref": "feature-branch"}, "merged": true}"#;
+
+fn pr_url() -> PrUrl {
+    github::parse_pr_url("https://github.com/owner/repo/pull/59").unwrap()
+}
+
+fn client(server: &StubServer) -> GithubClient {
+    GithubClient::new(
+        server.url.clone(),
+        Some(MISSING_GH.to_string()),
+        Some("test-token".to_string()),
+    )
+}
+
+#[test]
+fn test_fetch_pr_info_falls_back_to_token_when_gh_is_missing() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/repo/pulls/59",
+        StubResponse::json(200, PR_JSON),
+    )]);
+
+    let pr_info = client(&server).fetch_pr_info(&pr_url()).unwrap();
+
+    assert_eq!(pr_info.branch_name, "feature-branch");
+    assert!(pr_info.is_merged);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(
+        requests[0]
+            .to_lowercase()
+            .contains("authorization: bearer test-token"),
+        "Expected the token to be sent, got:\n{}",
+        requests[0]
+    );
+}
+
+#[test]
+fn test_fetch_pr_info_without_gh_or_token() {
+    let server = StubServer::start(vec![]);
+    let client = GithubClient::new(server.url.clone(), Some(MISSING_GH.to_string()), None);
+
+    let err = client.fetch_pr_info(&pr_url()).unwrap_err();
+
+    assert!(format!("{:#}", err).contains("GITHUB_TOKEN not set"));
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn test_fetch_pr_info_not_found() {
+    let server = StubServer::start(vec![]);
+
+    let err = client(&server).fetch_pr_info(&pr_url()).unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<GithubError>(),
+        Some(GithubError::NotFound { .. })
+    ));
+    assert!(format!("{:#}", err).contains("lacks access"));
+}
+
+#[test]
+fn test_fetch_pr_info_error_message() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/repo/pulls/59",
+        StubResponse::json(401, r#"{"message": "Bad credentials"}"#),
+    )]);
+
+    let err = client(&server).fetch_pr_info(&pr_url()).unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<GithubError>(),
+        Some(GithubError::Auth(_))
+    ));
+    assert!(format!("{:#}", err).contains("Bad credentials"));
+}
+
+#[test]
+fn test_fetch_pr_info_rate_limited() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/repo/pulls/59",
+        StubResponse::json(
+            403,
+            r#"{"message": "API rate limit exceeded for 127.0.0.1."}"#,
+        )
+        .with_header("x-ratelimit-remaining", "0")
+        .with_header("x-ratelimit-reset", "1700000000"),
+    )]);
+
+    let err = client(&server).fetch_pr_info(&pr_url()).unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<GithubError>(),
+        Some(GithubError::RateLimited { reset: Some(_) })
+    ));
+    assert!(format!("{:#}", err).contains("rate limited until"));
+}
+
+#[test]
+fn test_fetch_pr_info_secondary_rate_limit() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/repo/pulls/59",
+        StubResponse::json(
+            403,
+            r#"{"message": "You have exceeded a secondary rate limit."}"#,
+        )
+        .with_header("retry-after", "60"),
+    )]);
+
+    let err = client(&server).fetch_pr_info(&pr_url()).unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<GithubError>(),
+        Some(GithubError::RateLimited { reset: Some(_) })
+    ));
+}
+
+#[test]
+fn test_fetch_pr_info_sso_required() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/repo/pulls/59",
+        StubResponse::json(
+            403,
+            r#"{"message": "Resource protected by organization SAML enforcement."}"#,
+        )
+        .with_header(
+            "x-github-sso",
+            "required; url=https://github.com/orgs/acme/sso?authorization_request=abc",
+        ),
+    )]);
+
+    let err = client(&server).fetch_pr_info(&pr_url()).unwrap_err();
+
+    assert!(format!("{:#}", err).contains("Token lacks access to org acme"));
+    assert!(format!("{:#}", err).contains("https://github.com/orgs/acme/sso"));
+}
+
+#[test]
+fn test_fetch_pr_info_network_error() {
+    // Nothing listens on a port that was just released.
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let client = GithubClient::new(
+        format!("http://127.0.0.1:{}", port),
+        Some(MISSING_GH.to_string()),
+        Some("test-token".to_string()),
+    );
+
+    let err = client.fetch_pr_info(&pr_url()).unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<GithubError>(),
+        Some(GithubError::Network(_))
+    ));
+}
+
+#[test]
+fn test_fetch_pr_info_invalid_json() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/repo/pulls/59",
+        StubResponse::json(200, "<html>not json</html>"),
+    )]);
+
+    let err = client(&server).fetch_pr_info(&pr_url()).unwrap_err();
+
+    assert!(format!("{:#}", err).contains("Failed to parse GitHub API response"));
+}
+
+#[test]
+fn test_fetch_pr_status() {
+    let server = StubServer::start(vec![(
+        "POST /graphql",
+        StubResponse::json(
+            200,
+            r#"{"data":{"repository":{"pullRequests":{"nodes":[{
+                "number": 7, "state": "MERGED", "isDraft": false, "reviewDecision": "APPROVED",
+                "commits": {"nodes": [{"commit": {"statusCheckRollup": {"state": "SUCCESS"}}}]}
+            }]}}}}"#,
+        ),
+    )]);
+
+    let status = client(&server)
+        .fetch_pr_status("owner", "repo", "feature-branch")
+        .unwrap()
+        .expect("Expected a PR status");
+
+    assert_eq!(status.number, 7);
+    assert_eq!(status.state, PrState::Merged);
+    assert_eq!(status.ci, Some(CiStatus::Passing));
+    assert_eq!(
+        server.request_json(0)["variables"]["branch"],
+        "feature-branch"
+    );
+}
+
+#[test]
+fn test_create_pull_request_validation_error() {
+    let server = StubServer::start(vec![(
+        "POST /repos/owner/repo/pulls",
+        StubResponse::json(
+            422,
+            r#"{"message": "Validation Failed",
+                "errors": [{"message": "A pull request already exists for owner:feature."}]}"#,
+        ),
+    )]);
+    let new_pr = NewPullRequest {
+        title: "Add feature".to_string(),
+        body: String::new(),
+        head: "feature".to_string(),
+        base: "main".to_string(),
+        draft: true,
+    };
+
+    let err = client(&server)
+        .create_pull_request("owner", "repo", &new_pr)
+        .unwrap_err();
+
+    assert!(format!("{:#}", err).contains("already exists"));
+    assert_eq!(server.request_json(0)["draft"], true);
+}
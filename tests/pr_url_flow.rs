@@ -0,0 +1,152 @@
+mod common;
+
+use clap::Parser;
+use common::{StubResponse, StubServer};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, Terminal};
+use shanti::{app::App, cli::Args, config::Config, github::GithubClient};
+use tempfile::TempDir;
+
+struct Fixture {
+    app: App,
+    _repos_dir: TempDir,
+    _worktrees_dir: TempDir,
+    _data_dir: TempDir,
+}
+
+/// Builds an app over a repos dir holding an empty `demo` repository, talking
+/// to `server` instead of GitHub.
+fn app_with_stub(server: &StubServer) -> Fixture {
+    let repos_dir = tempfile::tempdir().expect("Could not create temporary directory");
+    let worktrees_dir = tempfile::tempdir().expect("Could not create temporary directory");
+    let data_dir = tempfile::tempdir().expect("Could not create temporary directory");
+    git2::Repository::init(repos_dir.path().join("demo")).expect("Could not init repository");
+
+    let mut args = Args::try_parse_from([
+        "shanti",
+        "--worktrees-dir",
+        worktrees_dir.path().to_str().unwrap(),
+        "--repos-dir",
+        repos_dir.path().to_str().unwrap(),
+        "--no-pr-status",
+    ])
+    .expect("Could not parse the arguments");
+    // Keeps the history and metadata of the test out of the user's data dir.
+    args.data_dir = Some(data_dir.path().to_path_buf());
+    let github = GithubClient::new(
+        server.url.clone(),
+        Some("shanti-test-missing-gh".to_string()),
+        Some("test-token".to_string()),
+    );
+
+    Fixture {
+        app: App::with_args(args, Config::default(), github),
+        _repos_dir: repos_dir,
+        _worktrees_dir: worktrees_dir,
+        _data_dir: data_dir,
+    }
+}
+
+/// Opens the PR popup, submits `url` and returns the rendered screen.
+fn submit_pr_url(app: &mut App, url: &str) -> String {
+    app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+    for c in url.chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut terminal = Terminal::new(TestBackend::new(140, 30)).unwrap();
+    terminal.draw(|f| app.draw(f)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content()
+        .chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_pr_url_opens_new_worktree_popup_with_pr_branch() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/demo/pulls/12",
+        StubResponse::json(200, r#"{"head": {"ref": "fix-login"}, "merged": false}"#),
+    )]);
+    let mut fixture = app_with_stub(&server);
+
+    let screen = submit_pr_url(&mut fixture.app, "https://github.com/owner/demo/pull/12");
+
+    assert!(screen.contains("New Worktree"), "{}", screen);
+    assert!(screen.contains("fix-login"), "{}", screen);
+}
+
+#[test]
+fn test_pr_url_of_merged_pr_warns() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/demo/pulls/12",
+        StubResponse::json(200, r#"{"head": {"ref": "fix-login"}, "merged": true}"#),
+    )]);
+    let mut fixture = app_with_stub(&server);
+
+    let screen = submit_pr_url(&mut fixture.app, "https://github.com/owner/demo/pull/12");
+
+    assert!(screen.contains("PR is merged"), "{}", screen);
+}
+
+#[test]
+fn test_pr_url_of_unknown_repository_asks_to_clone() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/other/pulls/3",
+        StubResponse::json(200, r#"{"head": {"ref": "feature"}, "merged": false}"#),
+    )]);
+    let mut fixture = app_with_stub(&server);
+
+    let screen = submit_pr_url(&mut fixture.app, "https://github.com/owner/other/pull/3");
+
+    assert!(screen.contains("Clone Repository"), "{}", screen);
+    assert!(
+        screen.contains("git@github.com:owner/other.git"),
+        "{}",
+        screen
+    );
+}
+
+#[test]
+fn test_pr_url_not_found_shows_error() {
+    let server = StubServer::start(vec![]);
+    let mut fixture = app_with_stub(&server);
+
+    let screen = submit_pr_url(&mut fixture.app, "https://github.com/owner/demo/pull/404");
+
+    assert!(screen.contains("Worktree from PR"), "{}", screen);
+    assert!(screen.contains("Not Found"), "{}", screen);
+}
+
+#[test]
+fn test_pr_url_rate_limited_shows_error() {
+    let server = StubServer::start(vec![(
+        "GET /repos/owner/demo/pulls/12",
+        StubResponse::json(
+            403,
+            r#"{"message": "API rate limit exceeded for 127.0.0.1."}"#,
+        )
+        .with_header("x-ratelimit-remaining", "0")
+        .with_header("x-ratelimit-reset", "1700000000"),
+    )]);
+    let mut fixture = app_with_stub(&server);
+
+    let screen = submit_pr_url(&mut fixture.app, "https://github.com/owner/demo/pull/12");
+
+    assert!(screen.contains("rate limited until"), "{}", screen);
+}
+
+#[test]
+fn test_invalid_pr_url_does_not_call_the_api() {
+    let server = StubServer::start(vec![]);
+    let mut fixture = app_with_stub(&server);
+
+    let screen = submit_pr_url(&mut fixture.app, "https://gitlab.com/owner/demo/pull/1");
+
+    assert!(screen.contains("Not a GitHub URL"), "{}", screen);
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::{StubResponse, StubServer};
+use shanti::tracker::{self, TrackerConfig, TrackerKind};
+
+const JIRA_ISSUE_JSON: &str = r#"{"key": "WEB-12", "fields": {"summary": "Fix login"}}"#;
+
+const LINEAR_ISSUE_JSON: &str = r#"{"data": {"issue": {
+    "identifier": "ENG-7",
+    "title": "Fix login",
+    "url": "https://linear.app/acme/issue/ENG-7/fix-login"
+}}}"#;
+
+/// Sets the tokens of both trackers. Tests run in parallel and share the
+/// environment, so every test sets the same values.
+fn set_tokens() {
+    std::env::set_var("JIRA_API_TOKEN", "jira-token");
+    std::env::set_var("JIRA_EMAIL", "dev@example.com");
+    std::env::set_var("LINEAR_API_KEY", "linear-key");
+}
+
+fn config(kind: TrackerKind, url: String) -> TrackerConfig {
+    TrackerConfig {
+        kind,
+        url: Some(url),
+        branch_template: "{key}-{slug}".to_string(),
+    }
+}
+
+#[test]
+fn test_fetch_jira_issue() {
+    set_tokens();
+    let server = StubServer::start(vec![(
+        "GET /rest/api/2/issue/APP-12?fields=summary",
+        StubResponse::json(200, JIRA_ISSUE_JSON),
+    )]);
+
+    let issue =
+        tracker::fetch_issue(&config(TrackerKind::Jira, server.url.clone()), "APP-12").unwrap();
+    assert_eq!(issue.key, "WEB-12");
+    assert_eq!(issue.title, "Fix login");
+    // Links to where the issue lives now, not the key that was typed.
+    assert_eq!(issue.url, format!("{}/browse/WEB-12", server.url));
+    // Basic auth with the email, as JIRA Cloud expects.
+    assert!(server.requests()[0].contains("Basic ZGV2QGV4YW1wbGUuY29tOmppcmEtdG9rZW4="));
+}
+
+#[test]
+fn test_fetch_jira_issue_auth_failure() {
+    set_tokens();
+    let server = StubServer::start(vec![(
+        "GET /rest/api/2/issue/APP-12?fields=summary",
+        StubResponse::json(401, r#"{"errorMessages": ["Unauthorized"]}"#),
+    )]);
+
+    let error =
+        tracker::fetch_issue(&config(TrackerKind::Jira, server.url.clone()), "APP-12").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Tracker auth failed — check JIRA_API_TOKEN"
+    );
+}
+
+#[test]
+fn test_fetch_jira_issue_not_found() {
+    set_tokens();
+    let server = StubServer::start(vec![]);
+
+    let error = tracker::fetch_issue(&config(TrackerKind::Jira, server.url.clone()), "APP-404")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Issue APP-404 not found");
+}
+
+#[test]
+fn test_fetch_linear_issue() {
+    set_tokens();
+    let server = StubServer::start(vec![("POST /", StubResponse::json(200, LINEAR_ISSUE_JSON))]);
+
+    let issue =
+        tracker::fetch_issue(&config(TrackerKind::Linear, server.url.clone()), "ENG-7").unwrap();
+    assert_eq!(issue.key, "ENG-7");
+    assert_eq!(issue.title, "Fix login");
+    assert_eq!(issue.url, "https://linear.app/acme/issue/ENG-7/fix-login");
+    assert_eq!(server.request_json(0)["variables"]["id"], "ENG-7");
+    assert!(server.requests()[0]
+        .to_lowercase()
+        .contains("authorization: linear-key"));
+}
+
+#[test]
+fn test_fetch_linear_issue_auth_failure() {
+    set_tokens();
+    let server = StubServer::start(vec![(
+        "POST /",
+        StubResponse::json(
+            401,
+            r#"{"errors": [{"message": "Authentication required"}]}"#,
+        ),
+    )]);
+
+    let error = tracker::fetch_issue(&config(TrackerKind::Linear, server.url.clone()), "ENG-7")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Tracker auth failed — check LINEAR_API_KEY"
+    );
+}
+
+#[test]
+fn test_fetch_linear_issue_not_found() {
+    set_tokens();
+    // Linear answers 200 with no issue for an unknown key.
+    let server = StubServer::start(vec![(
+        "POST /",
+        StubResponse::json(200, r#"{"data": {"issue": null}}"#),
+    )]);
+    let error = tracker::fetch_issue(&config(TrackerKind::Linear, server.url.clone()), "ENG-404")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Issue ENG-404 not found");
+
+    let server = StubServer::start(vec![]);
+    let error = tracker::fetch_issue(&config(TrackerKind::Linear, server.url.clone()), "ENG-404")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Issue ENG-404 not found");
+}
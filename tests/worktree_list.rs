@@ -0,0 +1,423 @@
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{backend::TestBackend, Terminal};
+use shanti::{app::App, cli::Args, config::Config, github::GithubClient};
+use tempfile::TempDir;
+
+struct Fixture {
+    app: App,
+    _repos_dir: TempDir,
+    _worktrees_dir: TempDir,
+    _data_dir: TempDir,
+}
+
+/// Creates `repo` with a single commit and a worktree per branch in
+/// `worktrees_dir/<repo>/<branch>`.
+fn repo_with_worktrees(repos_dir: &Path, worktrees_dir: &Path, repo: &str, branches: &[&str]) {
+    let git_repo = git2::Repository::init(repos_dir.join(repo)).unwrap();
+    let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree_id = git_repo.index().unwrap().write_tree().unwrap();
+    let tree = git_repo.find_tree(tree_id).unwrap();
+    let commit_id = git_repo
+        .commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+        .unwrap();
+    let commit = git_repo.find_commit(commit_id).unwrap();
+
+    std::fs::create_dir_all(worktrees_dir.join(repo)).unwrap();
+    for branch in branches {
+        let branch_ref = git_repo.branch(branch, &commit, false).unwrap();
+        let mut options = git2::WorktreeAddOptions::new();
+        options.reference(Some(branch_ref.get()));
+        git_repo
+            .worktree(
+                branch,
+                &worktrees_dir.join(repo).join(branch),
+                Some(&options),
+            )
+            .unwrap();
+    }
+}
+
+fn app_with_worktrees() -> Fixture {
+    app_with_config(Config::default())
+}
+
+fn app_with_config(config: Config) -> Fixture {
+    let repos_dir = tempfile::tempdir().expect("Could not create temporary directory");
+    let worktrees_dir = tempfile::tempdir().expect("Could not create temporary directory");
+    let data_dir = tempfile::tempdir().expect("Could not create temporary directory");
+    repo_with_worktrees(
+        repos_dir.path(),
+        worktrees_dir.path(),
+        "alpha",
+        &["login", "signup"],
+    );
+    repo_with_worktrees(repos_dir.path(), worktrees_dir.path(), "beta", &["search"]);
+
+    let mut args = Args::try_parse_from([
+        "shanti",
+        "--worktrees-dir",
+        worktrees_dir.path().to_str().unwrap(),
+        "--repos-dir",
+        repos_dir.path().to_str().unwrap(),
+        "--no-pr-status",
+    ])
+    .expect("Could not parse the arguments");
+    // Keeps the history and metadata of each test to itself and out of the
+    // user's data dir.
+    args.data_dir = Some(data_dir.path().to_path_buf());
+    let github = GithubClient::new(
+        "http://127.0.0.1:9".to_string(),
+        Some("shanti-test-missing-gh".to_string()),
+        None,
+    );
+
+    Fixture {
+        app: App::with_args(args, config, github),
+        _repos_dir: repos_dir,
+        _worktrees_dir: worktrees_dir,
+        _data_dir: data_dir,
+    }
+}
+
+fn press(app: &mut App, code: KeyCode) {
+    app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+fn show_grouped(app: &mut App) {
+    press(app, KeyCode::Char('v'));
+    assert!(render(app).contains("▾ alpha"));
+}
+
+fn click(app: &mut App, column: u16, row: u16) {
+    app.handle_mouse(MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    });
+}
+
+/// Column and row of the first occurrence of `text` on the screen.
+fn position_of(screen: &str, text: &str) -> (u16, u16) {
+    screen
+        .lines()
+        .enumerate()
+        .find_map(|(row, line)| {
+            let byte = line.find(text)?;
+            Some((line[..byte].chars().count() as u16, row as u16))
+        })
+        .unwrap_or_else(|| panic!("{:?} is not on the screen:\n{}", text, screen))
+}
+
+/// Lets the background work of `app` run until `text` is on the screen, or
+/// ten seconds passed, and returns the screen.
+fn wait_for(app: &mut App, text: &str) -> String {
+    wait_until(app, |screen| screen.contains(text))
+}
+
+fn wait_until(app: &mut App, done: impl Fn(&str) -> bool) -> String {
+    let started = Instant::now();
+    while !done(&render(app)) && started.elapsed() < Duration::from_secs(10) {
+        app.on_tick();
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    render(app)
+}
+
+fn render(app: &mut App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    terminal.draw(|f| app.draw(f)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer
+        .content()
+        .chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_grouped_view_shows_repository_headers() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+    show_grouped(app);
+
+    let screen = render(app);
+    assert!(screen.contains("▾ alpha (2)"), "{}", screen);
+    assert!(screen.contains("▾ beta (1)"), "{}", screen);
+    assert!(screen.contains("login"), "{}", screen);
+    assert!(!screen.contains("alpha / login"), "{}", screen);
+}
+
+#[test]
+fn test_collapse_and_filter_across_groups() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+    show_grouped(app);
+
+    // The first worktree of alpha is selected; collapse its repository.
+    press(app, KeyCode::Char('h'));
+    let screen = render(app);
+    assert!(screen.contains("▸ alpha (2)"), "{}", screen);
+    assert!(!screen.contains("signup"), "{}", screen);
+
+    // Filtering shows matches from collapsed repositories too.
+    press(app, KeyCode::Char('/'));
+    for c in "signup".chars() {
+        press(app, KeyCode::Char(c));
+    }
+    let screen = render(app);
+    assert!(screen.contains("signup"), "{}", screen);
+    assert!(!screen.contains("beta"), "{}", screen);
+
+    for _ in 0.."signup".len() {
+        press(app, KeyCode::Backspace);
+    }
+    press(app, KeyCode::Esc);
+    press(app, KeyCode::Char('g'));
+    press(app, KeyCode::Char('l'));
+    let screen = render(app);
+    assert!(screen.contains("▾ alpha (2)"), "{}", screen);
+    assert!(screen.contains("signup"), "{}", screen);
+}
+
+#[test]
+fn test_filter_qualifiers() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+
+    press(app, KeyCode::Char('/'));
+    for c in "repo:beta !is:dirty".chars() {
+        press(app, KeyCode::Char(c));
+    }
+    let screen = render(app);
+    assert!(screen.contains("search"), "{}", screen);
+    assert!(!screen.contains("login"), "{}", screen);
+
+    for c in " is:dirty".chars() {
+        press(app, KeyCode::Char(c));
+    }
+    let screen = render(app);
+    assert!(!screen.contains("search"), "{}", screen);
+}
+
+#[test]
+fn test_delete_marked_worktrees() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+
+    press(app, KeyCode::Char('/'));
+    for c in "repo:alpha".chars() {
+        press(app, KeyCode::Char(c));
+    }
+    press(app, KeyCode::Esc);
+    press(app, KeyCode::Char('A'));
+    let screen = render(app);
+    assert!(screen.contains("2 marked"), "{}", screen);
+
+    press(app, KeyCode::Char('d'));
+    let screen = render(app);
+    assert!(screen.contains("Delete these 2 worktrees?"), "{}", screen);
+    assert!(screen.contains("• alpha / login"), "{}", screen);
+    assert!(screen.contains("• alpha / signup"), "{}", screen);
+
+    press(app, KeyCode::Enter);
+    let screen = render(app);
+    assert!(screen.contains("2 ok, 0 failed"), "{}", screen);
+
+    press(app, KeyCode::Esc);
+    let screen = render(app);
+    assert!(!screen.contains("login"), "{}", screen);
+    assert!(!screen.contains("signup"), "{}", screen);
+    assert!(!fixture._worktrees_dir.path().join("alpha/login").exists());
+}
+
+#[test]
+fn test_fetch_marked_worktrees_in_background() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+
+    press(app, KeyCode::Char('A'));
+    press(app, KeyCode::Char('F'));
+    press(app, KeyCode::Enter);
+    let screen = render(app);
+    assert!(screen.contains("Fetching 3 worktree(s)"), "{}", screen);
+    // The repositories have no origin to fetch from.
+    let screen = wait_for(app, "0 ok, 3 failed");
+    assert!(screen.contains("0 ok, 3 failed"), "{}", screen);
+    assert!(screen.contains("Could not fetch from origin"), "{}", screen);
+}
+
+#[test]
+fn test_delete_the_marked_worktree_not_the_selected_one() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+
+    let (column, row) = position_of(&render(app), "login");
+    click(app, column, row);
+    press(app, KeyCode::Char(' '));
+    let (column, row) = position_of(&render(app), "signup");
+    click(app, column, row);
+    press(app, KeyCode::Char('d'));
+    let screen = render(app);
+    assert!(screen.contains("Delete this worktree?"), "{}", screen);
+    assert!(screen.contains("alpha/login"), "{}", screen);
+
+    press(app, KeyCode::Enter);
+    let worktrees_dir = fixture._worktrees_dir.path();
+    assert!(!worktrees_dir.join("alpha/login").exists());
+    assert!(worktrees_dir.join("alpha/signup").exists());
+}
+
+#[test]
+fn test_delete_keeps_pinned_worktree() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+
+    let (column, row) = position_of(&render(app), "signup");
+    click(app, column, row);
+    press(app, KeyCode::Char('f'));
+    press(app, KeyCode::Char('d'));
+    press(app, KeyCode::Enter);
+    let screen = render(app);
+    assert!(screen.contains("Worktree is pinned"), "{}", screen);
+    assert!(fixture._worktrees_dir.path().join("alpha/signup").exists());
+}
+
+#[test]
+fn test_delete_hooks() {
+    let config = Config::parse(
+        r#"
+        [hooks]
+        pre_delete = ["echo checking $SHANTI_BRANCH", "test ! -e keep"]
+        post_delete = ["touch deleted-$SHANTI_BRANCH"]
+        "#,
+    )
+    .unwrap();
+    let mut fixture = app_with_config(config);
+    let app = &mut fixture.app;
+    let worktree = fixture._worktrees_dir.path().join("alpha/signup");
+    std::fs::write(worktree.join("keep"), "").unwrap();
+
+    let (column, row) = position_of(&render(app), "signup");
+    click(app, column, row);
+    press(app, KeyCode::Char('x'));
+    let screen = render(app);
+    assert!(
+        screen.contains("Running pre_delete hooks of alpha / signup…"),
+        "{}",
+        screen
+    );
+    let screen = wait_for(app, "pre_delete hook failed");
+    assert!(screen.contains("checking signup"), "{}", screen);
+    assert!(worktree.exists());
+
+    press(app, KeyCode::Char('D'));
+    // The status line ends with … while the hooks run.
+    let screen = wait_until(app, |screen| !screen.contains("hooks of alpha / signup…"));
+    assert!(!screen.contains("Deleted, but"), "{}", screen);
+    assert!(!worktree.exists());
+    // Runs in the repository, as the worktree is gone.
+    let repo = fixture._repos_dir.path().join("alpha");
+    assert!(repo.join("deleted-signup").exists());
+}
+
+#[test]
+fn test_open_in_foreground_editor_keeps_running() {
+    let config = Config::parse(
+        r#"
+        [open]
+        editor = "vi -R"
+        foreground = true
+        "#,
+    )
+    .unwrap();
+    let mut fixture = app_with_config(config);
+    let app = &mut fixture.app;
+
+    let (column, row) = position_of(&render(app), "signup");
+    click(app, column, row);
+    press(app, KeyCode::Char('e'));
+    let command = app.take_foreground_command().expect("Expected the editor");
+    let args: Vec<_> = command.get_args().collect();
+    assert_eq!(command.get_program(), "vi");
+    assert!(args[1].to_string_lossy().ends_with("alpha/signup"));
+    assert!(app.take_foreground_command().is_none());
+    assert!(app.selected_path.is_none());
+    assert!(render(app).contains("signup"));
+}
+
+#[test]
+fn test_mouse_selects_and_opens_worktrees() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+
+    let (column, row) = position_of(&render(app), "search");
+    click(app, column, row);
+    assert_eq!(app.selected_path, None);
+    click(app, column, row);
+    let path = app.selected_path.clone().unwrap();
+    assert!(path.ends_with("beta/search"), "{}", path);
+}
+
+#[test]
+fn test_mouse_clicks_confirm_buttons() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+
+    let (column, row) = position_of(&render(app), "signup");
+    click(app, column, row);
+    press(app, KeyCode::Char('d'));
+    let (column, row) = position_of(&render(app), "cancel");
+    click(app, column, row);
+    assert!(!render(app).contains("Delete this worktree?"));
+    assert!(fixture._worktrees_dir.path().join("alpha/signup").exists());
+
+    press(app, KeyCode::Char('d'));
+    let (column, row) = position_of(&render(app), "[Enter] confirm");
+    click(app, column, row);
+    assert!(!fixture._worktrees_dir.path().join("alpha/signup").exists());
+}
+
+#[test]
+fn test_run_command_in_marked_worktrees() {
+    let mut fixture = app_with_worktrees();
+    let app = &mut fixture.app;
+
+    press(app, KeyCode::Char('/'));
+    for c in "repo:alpha".chars() {
+        press(app, KeyCode::Char(c));
+    }
+    press(app, KeyCode::Esc);
+    press(app, KeyCode::Char('A'));
+    press(app, KeyCode::Char('!'));
+    let screen = render(app);
+    assert!(
+        screen.contains("Shell command to run in each of the 2 worktrees:"),
+        "{}",
+        screen
+    );
+    for c in "echo hi-$SHANTI_BRANCH".chars() {
+        press(app, KeyCode::Char(c));
+    }
+    press(app, KeyCode::Enter);
+
+    let screen = wait_for(app, "2 passed, 0 failed");
+    assert!(screen.contains("alpha/login  │ hi-login"), "{}", screen);
+    assert!(screen.contains("alpha/signup │ hi-signup"), "{}", screen);
+    assert!(screen.contains("2 passed, 0 failed"), "{}", screen);
+
+    press(app, KeyCode::Esc);
+    let screen = render(app);
+    assert!(
+        screen.contains("`echo hi-$SHANTI_BRANCH` passed in 2 and failed in 0 worktree(s)"),
+        "{}",
+        screen
+    );
+}